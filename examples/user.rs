@@ -1,5 +1,5 @@
 use clap::{App, Arg};
-use github_stats::User;
+use github_stats::UserProfile;
 
 #[tokio::main]
 async fn main() {
@@ -12,7 +12,9 @@ async fn main() {
     let user = matches.value_of("user").unwrap();
 
     println!("Getting user {}", user);
-    let user = User::new(user, "github-stats-rs example").await.unwrap();
+    let profile = UserProfile::new(user, "github-stats-rs example").await.unwrap();
 
-    println!("API URL: {}", user.url());
+    println!("API URL: {}", profile.user().url());
+    println!("Public repos: {}", profile.public_repos());
+    println!("Followers: {}", profile.followers());
 }
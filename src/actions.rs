@@ -0,0 +1,518 @@
+//! For getting [Github Actions] workflows and their runs.
+//!
+//! [Github Actions]: https://docs.github.com/en/actions
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::request::List;
+use crate::User;
+
+/// Most workflow runs [Github] lists when they are filtered, such as by
+/// [`created_between`](List::created_between), across every page.
+///
+/// [Github]: https://github.com/
+pub const MAX_FILTERED_RUNS: usize = 1000;
+
+/// A workflow defined in the repository's `.github/workflows` folder.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Workflow {
+    id: u64,
+    node_id: String,
+    name: String,
+    path: String,
+    /// Such as `active` or `disabled_manually`.
+    state: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    url: String,
+    html_url: String,
+    badge_url: String,
+}
+
+/// A single run of a [`Workflow`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WorkflowRun {
+    id: u64,
+    node_id: String,
+    name: Option<String>,
+    workflow_id: u64,
+    head_branch: Option<String>,
+    head_sha: String,
+    path: String,
+    run_number: u64,
+    /// `1` for the first attempt, and increased by each rerun.
+    #[serde(default = "first_attempt")]
+    run_attempt: u64,
+    /// What triggered the run, such as `push` or `pull_request`.
+    event: String,
+    status: Option<Status>,
+    /// `None` until the run is completed.
+    conclusion: Option<Conclusion>,
+    /// `None` if the account was deleted.
+    #[serde(default)]
+    actor: Option<User>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// When the latest attempt started.
+    run_started_at: Option<DateTime<Utc>>,
+    url: String,
+    html_url: String,
+    jobs_url: String,
+}
+
+/// A job in a [`WorkflowRun`], such as one entry of a build matrix.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Job {
+    id: u64,
+    node_id: String,
+    run_id: u64,
+    /// Attempt of the run that this job belongs to.
+    #[serde(default = "first_attempt")]
+    run_attempt: u64,
+    head_sha: String,
+    name: String,
+    #[serde(default)]
+    workflow_name: Option<String>,
+    status: Option<Status>,
+    /// `None` until the job is completed.
+    conclusion: Option<Conclusion>,
+    started_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    /// Labels of the runner, such as `ubuntu-latest`.
+    #[serde(default)]
+    labels: Vec<String>,
+    url: String,
+    html_url: Option<String>,
+}
+
+/// Which attempts to list jobs for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobFilter {
+    Latest,
+    All,
+}
+
+impl fmt::Display for JobFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use JobFilter::*;
+        write!(f, "{}", match self {
+            Latest => "latest",
+            All => "all",
+        })
+    }
+}
+
+fn first_attempt() -> u64 {
+    1
+}
+
+/// Progress of a run, job or check.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Requested,
+    Queued,
+    Pending,
+    Waiting,
+    InProgress,
+    Completed,
+    /// A status that was added to [Github] after this was written.
+    ///
+    /// [Github]: https://github.com/
+    #[serde(other)]
+    Unknown,
+}
+
+/// Outcome of a completed run, job or check.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Conclusion {
+    Success,
+    Failure,
+    Neutral,
+    Cancelled,
+    Skipped,
+    TimedOut,
+    ActionRequired,
+    Stale,
+    StartupFailure,
+    /// A conclusion that was added to [Github] after this was written.
+    ///
+    /// [Github]: https://github.com/
+    #[serde(other)]
+    Unknown,
+}
+
+impl Conclusion {
+    /// If the conclusion means something went wrong, rather than that the
+    /// run was cancelled or skipped.
+    pub fn is_failure(&self) -> bool {
+        use Conclusion::*;
+        matches!(self, Failure | TimedOut | StartupFailure)
+    }
+}
+
+impl fmt::Display for Conclusion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Conclusion::*;
+        write!(f, "{}", match self {
+            Success => "success",
+            Failure => "failure",
+            Neutral => "neutral",
+            Cancelled => "cancelled",
+            Skipped => "skipped",
+            TimedOut => "timed_out",
+            ActionRequired => "action_required",
+            Stale => "stale",
+            StartupFailure => "startup_failure",
+            Unknown => "unknown",
+        })
+    }
+}
+
+impl Workflow {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Such as `.github/workflows/ci.yml`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Such as `active` or `disabled_manually`.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn badge_url(&self) -> &str {
+        &self.badge_url
+    }
+}
+
+impl WorkflowRun {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    pub fn workflow_id(&self) -> u64 {
+        self.workflow_id
+    }
+
+    pub fn head_branch(&self) -> &Option<String> {
+        &self.head_branch
+    }
+
+    pub fn head_sha(&self) -> &str {
+        &self.head_sha
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn run_number(&self) -> u64 {
+        self.run_number
+    }
+
+    /// `1` for the first attempt, and increased by each rerun.
+    pub fn run_attempt(&self) -> u64 {
+        self.run_attempt
+    }
+
+    /// What triggered the run, such as `push` or `pull_request`.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    pub fn status(&self) -> Option<Status> {
+        self.status
+    }
+
+    /// `None` until the run is completed.
+    pub fn conclusion(&self) -> Option<Conclusion> {
+        self.conclusion
+    }
+
+    /// `None` if the account was deleted.
+    pub fn actor(&self) -> &Option<User> {
+        &self.actor
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    /// When the latest attempt started.
+    pub fn run_started_at(&self) -> &Option<DateTime<Utc>> {
+        &self.run_started_at
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn jobs_url(&self) -> &str {
+        &self.jobs_url
+    }
+}
+
+impl Job {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn run_id(&self) -> u64 {
+        self.run_id
+    }
+
+    /// Attempt of the run that this job belongs to.
+    pub fn run_attempt(&self) -> u64 {
+        self.run_attempt
+    }
+
+    pub fn head_sha(&self) -> &str {
+        &self.head_sha
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn workflow_name(&self) -> &Option<String> {
+        &self.workflow_name
+    }
+
+    pub fn status(&self) -> Option<Status> {
+        self.status
+    }
+
+    /// `None` until the job is completed.
+    pub fn conclusion(&self) -> Option<Conclusion> {
+        self.conclusion
+    }
+
+    pub fn started_at(&self) -> &DateTime<Utc> {
+        &self.started_at
+    }
+
+    pub fn completed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.completed_at
+    }
+
+    /// Labels of the runner, such as `ubuntu-latest`.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &Option<String> {
+        &self.html_url
+    }
+}
+
+/// Filters for [`Repo::workflow_runs`](crate::Repo::workflow_runs).
+impl List<WorkflowRun> {
+    /// Only runs for pushes to, or pull requests from, this branch.
+    pub fn branch(self, branch: &str) -> Self {
+        self.param("branch", branch)
+    }
+
+    /// Only runs triggered by this event, such as `push`.
+    pub fn event(self, event: &str) -> Self {
+        self.param("event", event)
+    }
+
+    /// Only runs with this status or conclusion, such as `in_progress` or
+    /// `failure`.
+    pub fn status(self, status: &str) -> Self {
+        self.param("status", status)
+    }
+
+    /// Only runs started by this user.
+    pub fn actor(self, username: &str) -> Self {
+        self.param("actor", username)
+    }
+
+    /// Only runs for this commit.
+    pub fn head_sha(self, sha: &str) -> Self {
+        self.param("head_sha", sha)
+    }
+
+    /// Only runs created in this range, written with [Github]'s search
+    /// syntax, such as `>=2020-01-01` or `2020-01-01..2020-02-01`.
+    ///
+    /// [Github] only lists the latest 1000 matching runs.
+    ///
+    /// [Github]: https://github.com/
+    pub fn created(self, range: &str) -> Self {
+        self.param("created", range)
+    }
+
+    /// Only runs created between `start` and `end`, inclusive.
+    ///
+    /// [Github] only lists the latest 1000 matching runs.
+    ///
+    /// [Github]: https://github.com/
+    pub fn created_between(self, start: &DateTime<Utc>, end: &DateTime<Utc>) -> Self {
+        self.param("created", format!(
+            "{}..{}",
+            start.to_rfc3339_opts(SecondsFormat::Secs, true),
+            end.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ))
+    }
+}
+
+/// Filters for [`Repo::run_jobs`](crate::Repo::run_jobs).
+impl List<Job> {
+    /// Defaults to [`JobFilter::Latest`], which leaves out jobs of earlier
+    /// attempts.
+    pub fn filter(self, filter: JobFilter) -> Self {
+        self.param("filter", filter)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::repository::tests::repo;
+    use crate::user::tests::user;
+
+    /// A completed run of the `CI` workflow, as [Github] lists it.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn run(
+        id: u64,
+        head_sha: &str,
+        run_attempt: u64,
+        conclusion: Option<&str>,
+        created_at: &str,
+        run_started_at: &str,
+        updated_at: &str,
+    ) -> Value {
+        json!({
+            "id": id,
+            "node_id": "MDEyOldvcmtmbG93IFJ1bjU=",
+            "name": "CI",
+            "workflow_id": 159038,
+            "head_branch": "master",
+            "head_sha": head_sha,
+            "path": ".github/workflows/ci.yml",
+            "run_number": id,
+            "run_attempt": run_attempt,
+            "event": "push",
+            "status": if conclusion.is_some() { "completed" } else { "in_progress" },
+            "conclusion": conclusion,
+            "actor": user("octocat"),
+            "created_at": created_at,
+            "updated_at": updated_at,
+            "run_started_at": run_started_at,
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/actions/runs/{}", id),
+            "html_url": format!("https://github.com/octocat/Hello-World/actions/runs/{}", id),
+            "jobs_url": format!("https://api.github.com/repos/octocat/Hello-World/actions/runs/{}/jobs", id),
+        })
+    }
+
+    /// A job of the run with ID `run_id`.
+    pub(crate) fn job(
+        id: u64,
+        run_id: u64,
+        run_attempt: u64,
+        head_sha: &str,
+        name: &str,
+        conclusion: Option<&str>,
+        started_at: &str,
+    ) -> Value {
+        json!({
+            "id": id,
+            "node_id": "MDg6Q2hlY2tSdW4zOTk0NDQ0OTY=",
+            "run_id": run_id,
+            "run_attempt": run_attempt,
+            "head_sha": head_sha,
+            "name": name,
+            "workflow_name": "CI",
+            "status": if conclusion.is_some() { "completed" } else { "in_progress" },
+            "conclusion": conclusion,
+            "started_at": started_at,
+            "completed_at": conclusion.map(|_| started_at),
+            "labels": ["ubuntu-latest"],
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/actions/jobs/{}", id),
+            "html_url": format!("https://github.com/octocat/Hello-World/runs/{}", id),
+        })
+    }
+
+    #[test]
+    fn built_runs() {
+        const EXPECTED: &str = "https://api.github.com/repos/octocat/Hello-World/actions/runs?branch=master&created=2020-01-01T00%3A00%3A00Z..2020-02-01T00%3A00%3A00Z&per_page=100";
+        let start = "2020-01-01T00:00:00Z".parse().unwrap();
+        let end = "2020-02-01T00:00:00Z".parse().unwrap();
+        let runs = repo(json!({}))
+            .workflow_runs()
+            .branch("master")
+            .created_between(&start, &end);
+
+        assert_eq!(EXPECTED, runs.to_string());
+    }
+
+    #[test]
+    fn unknown_conclusion() {
+        let mut run = run(1, "acb5820ced9479c074f688cc328bf03f341a511d", 1, Some("success"), "2020-01-22T19:33:08Z", "2020-01-22T19:33:08Z", "2020-01-22T19:35:08Z");
+        run["conclusion"] = json!("exploded");
+        let run: WorkflowRun = serde_json::from_value(run).unwrap();
+
+        assert_eq!(Some(Conclusion::Unknown), run.conclusion());
+        assert_eq!(Some(Status::Completed), run.status());
+    }
+}
@@ -0,0 +1,690 @@
+//! For getting Dependabot, code scanning and secret scanning alerts, and
+//! rolling them up by severity, state and age.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::metrics::Distribution;
+use crate::request::List;
+
+/// How severe a vulnerability is.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Severity::*;
+        write!(f, "{}", match self {
+            Low => "low",
+            Medium => "medium",
+            High => "high",
+            Critical => "critical",
+        })
+    }
+}
+
+/// Which tool raised an alert.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AlertKind {
+    Dependabot,
+    CodeScanning,
+    SecretScanning,
+}
+
+/// The repository an alert belongs to, only included when alerts are listed
+/// for an organization.
+#[derive(Debug, Deserialize, Serialize)]
+struct AlertRepository {
+    full_name: String,
+}
+
+/// A vulnerable dependency found by Dependabot.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DependabotAlert {
+    number: u64,
+    /// Such as `open`, `dismissed`, `fixed` or `auto_dismissed`.
+    state: String,
+    dependency: Dependency,
+    security_advisory: SecurityAdvisory,
+    url: String,
+    html_url: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    dismissed_at: Option<DateTime<Utc>>,
+    fixed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    auto_dismissed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    repository: Option<AlertRepository>,
+}
+
+/// The dependency that a [`DependabotAlert`] is about.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Dependency {
+    package: AdvisoryPackage,
+    manifest_path: String,
+    /// `development` or `runtime`.
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdvisoryPackage {
+    /// Such as `npm` or `rust`.
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SecurityAdvisory {
+    ghsa_id: String,
+    cve_id: Option<String>,
+    summary: String,
+    severity: Severity,
+}
+
+/// A problem in the code found by code scanning, such as CodeQL.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodeScanningAlert {
+    number: u64,
+    /// Such as `open`, `dismissed` or `fixed`.
+    state: String,
+    rule: CodeScanningRule,
+    tool: CodeScanningTool,
+    url: String,
+    html_url: String,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+    dismissed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    fixed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    repository: Option<AlertRepository>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodeScanningRule {
+    id: Option<String>,
+    name: Option<String>,
+    description: String,
+    /// `none`, `note`, `warning` or `error`.
+    severity: Option<String>,
+    /// Only set for security rules.
+    #[serde(default)]
+    security_severity_level: Option<Severity>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodeScanningTool {
+    /// Such as `CodeQL`.
+    name: String,
+    version: Option<String>,
+}
+
+/// A secret, such as a token, committed to the repository.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SecretScanningAlert {
+    number: u64,
+    /// `open` or `resolved`.
+    state: String,
+    /// Such as `revoked` or `false_positive`.
+    resolution: Option<String>,
+    /// Such as `github_personal_access_token`.
+    secret_type: String,
+    secret_type_display_name: Option<String>,
+    url: String,
+    html_url: String,
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    updated_at: Option<DateTime<Utc>>,
+    resolved_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    repository: Option<AlertRepository>,
+}
+
+/// The parts of any alert needed by an [`AlertRollup`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlertSummary {
+    kind: AlertKind,
+    /// Only known when alerts are listed for an organization.
+    repository: Option<String>,
+    state: String,
+    /// `None` for secret scanning alerts, and code scanning alerts of rules
+    /// that aren't about security.
+    severity: Option<Severity>,
+    created_at: DateTime<Utc>,
+}
+
+impl AlertSummary {
+    pub fn kind(&self) -> AlertKind {
+        self.kind
+    }
+
+    /// Only known when alerts are listed for an organization.
+    pub fn repository(&self) -> &Option<String> {
+        &self.repository
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// `None` for secret scanning alerts, and code scanning alerts of rules
+    /// that aren't about security.
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state == "open"
+    }
+}
+
+impl DependabotAlert {
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Such as `open`, `dismissed`, `fixed` or `auto_dismissed`.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn dependency(&self) -> &Dependency {
+        &self.dependency
+    }
+
+    pub fn security_advisory(&self) -> &SecurityAdvisory {
+        &self.security_advisory
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn dismissed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.dismissed_at
+    }
+
+    pub fn fixed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.fixed_at
+    }
+
+    pub fn auto_dismissed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.auto_dismissed_at
+    }
+
+    /// Such as `octocat/Hello-World`. Only known when alerts are listed for
+    /// an organization.
+    pub fn repository(&self) -> Option<&str> {
+        self.repository.as_ref().map(|r| r.full_name.as_str())
+    }
+
+    pub fn summary(&self) -> AlertSummary {
+        AlertSummary {
+            kind: AlertKind::Dependabot,
+            repository: self.repository().map(String::from),
+            state: self.state.clone(),
+            severity: Some(self.security_advisory.severity),
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl Dependency {
+    pub fn package(&self) -> &AdvisoryPackage {
+        &self.package
+    }
+
+    pub fn manifest_path(&self) -> &str {
+        &self.manifest_path
+    }
+
+    /// `development` or `runtime`.
+    pub fn scope(&self) -> &Option<String> {
+        &self.scope
+    }
+}
+
+impl AdvisoryPackage {
+    /// Such as `npm` or `rust`.
+    pub fn ecosystem(&self) -> &str {
+        &self.ecosystem
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl SecurityAdvisory {
+    pub fn ghsa_id(&self) -> &str {
+        &self.ghsa_id
+    }
+
+    pub fn cve_id(&self) -> &Option<String> {
+        &self.cve_id
+    }
+
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+impl CodeScanningAlert {
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Such as `open`, `dismissed` or `fixed`.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn rule(&self) -> &CodeScanningRule {
+        &self.rule
+    }
+
+    pub fn tool(&self) -> &CodeScanningTool {
+        &self.tool
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &Option<DateTime<Utc>> {
+        &self.updated_at
+    }
+
+    pub fn dismissed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.dismissed_at
+    }
+
+    pub fn fixed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.fixed_at
+    }
+
+    /// Such as `octocat/Hello-World`. Only known when alerts are listed for
+    /// an organization.
+    pub fn repository(&self) -> Option<&str> {
+        self.repository.as_ref().map(|r| r.full_name.as_str())
+    }
+
+    pub fn summary(&self) -> AlertSummary {
+        AlertSummary {
+            kind: AlertKind::CodeScanning,
+            repository: self.repository().map(String::from),
+            state: self.state.clone(),
+            severity: self.rule.security_severity_level,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl CodeScanningRule {
+    pub fn id(&self) -> &Option<String> {
+        &self.id
+    }
+
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// `none`, `note`, `warning` or `error`.
+    pub fn severity(&self) -> &Option<String> {
+        &self.severity
+    }
+
+    /// Only set for security rules.
+    pub fn security_severity_level(&self) -> Option<Severity> {
+        self.security_severity_level
+    }
+}
+
+impl CodeScanningTool {
+    /// Such as `CodeQL`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> &Option<String> {
+        &self.version
+    }
+}
+
+impl SecretScanningAlert {
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// `open` or `resolved`.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Such as `revoked` or `false_positive`.
+    pub fn resolution(&self) -> &Option<String> {
+        &self.resolution
+    }
+
+    /// Such as `github_personal_access_token`.
+    pub fn secret_type(&self) -> &str {
+        &self.secret_type
+    }
+
+    pub fn secret_type_display_name(&self) -> &Option<String> {
+        &self.secret_type_display_name
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &Option<DateTime<Utc>> {
+        &self.updated_at
+    }
+
+    pub fn resolved_at(&self) -> &Option<DateTime<Utc>> {
+        &self.resolved_at
+    }
+
+    /// Such as `octocat/Hello-World`. Only known when alerts are listed for
+    /// an organization.
+    pub fn repository(&self) -> Option<&str> {
+        self.repository.as_ref().map(|r| r.full_name.as_str())
+    }
+
+    pub fn summary(&self) -> AlertSummary {
+        AlertSummary {
+            kind: AlertKind::SecretScanning,
+            repository: self.repository().map(String::from),
+            state: self.state.clone(),
+            severity: None,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Filters for [`Repo::dependabot_alerts`](crate::Repo::dependabot_alerts).
+impl List<DependabotAlert> {
+    /// Such as `open` or `fixed`. Several can be separated with commas.
+    pub fn state(self, state: &str) -> Self {
+        self.param("state", state)
+    }
+
+    pub fn severities(self, severities: &[Severity]) -> Self {
+        let severities: Vec<_> = severities.iter().map(Severity::to_string).collect();
+        self.param("severity", severities.join(","))
+    }
+
+    /// Such as `npm` or `rust`. Several can be separated with commas.
+    pub fn ecosystem(self, ecosystem: &str) -> Self {
+        self.param("ecosystem", ecosystem)
+    }
+}
+
+/// Filters for
+/// [`Repo::code_scanning_alerts`](crate::Repo::code_scanning_alerts).
+impl List<CodeScanningAlert> {
+    /// Such as `open` or `fixed`.
+    pub fn state(self, state: &str) -> Self {
+        self.param("state", state)
+    }
+
+    /// Such as `CodeQL`.
+    pub fn tool_name(self, name: &str) -> Self {
+        self.param("tool_name", name)
+    }
+}
+
+/// Filters for
+/// [`Repo::secret_scanning_alerts`](crate::Repo::secret_scanning_alerts).
+impl List<SecretScanningAlert> {
+    /// `open` or `resolved`.
+    pub fn state(self, state: &str) -> Self {
+        self.param("state", state)
+    }
+}
+
+/// Counts of alerts by severity, state and age.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Utc;
+/// use github_stats::alert::{AlertRollup, DependabotAlert};
+/// use github_stats::org;
+///
+/// let alerts = org::dependabot_alerts("rust-lang")
+///     .authorization("<my token>")
+///     .fetch("<my user agent>")
+///     .await?;
+/// let rollup = AlertRollup::new(alerts.iter().map(DependabotAlert::summary), &Utc::now());
+///
+/// for (repo, count) in rollup.open_critical_by_repository() {
+///     println!("{}: {}", repo, count);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AlertRollup {
+    alerts: Vec<AlertSummary>,
+    open_age: Distribution,
+}
+
+impl AlertRollup {
+    /// Ages are measured up to `now`.
+    pub fn new<I: IntoIterator<Item = AlertSummary>>(alerts: I, now: &DateTime<Utc>) -> Self {
+        let alerts: Vec<_> = alerts.into_iter().collect();
+        let open_age = alerts
+            .iter()
+            .filter(|a| a.is_open())
+            .map(|a| *now - a.created_at)
+            .collect();
+        AlertRollup { alerts, open_age }
+    }
+
+    pub fn alerts(&self) -> &[AlertSummary] {
+        &self.alerts
+    }
+
+    pub fn open(&self) -> usize {
+        self.alerts.iter().filter(|a| a.is_open()).count()
+    }
+
+    /// Open alerts per severity. Alerts without a severity are under `None`.
+    pub fn open_by_severity(&self) -> BTreeMap<Option<Severity>, usize> {
+        let mut counts = BTreeMap::new();
+        for alert in self.alerts.iter().filter(|a| a.is_open()) {
+            *counts.entry(alert.severity).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Alerts per state, such as `open` or `fixed`.
+    pub fn by_state(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for alert in &self.alerts {
+            *counts.entry(alert.state.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn by_kind(&self) -> BTreeMap<AlertKind, usize> {
+        let mut counts = BTreeMap::new();
+        for alert in &self.alerts {
+            *counts.entry(alert.kind).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// How long open alerts have been open.
+    pub fn open_age(&self) -> &Distribution {
+        &self.open_age
+    }
+
+    /// Open alerts that have been open for longer than `age`.
+    pub fn open_older_than(&self, age: Duration) -> usize {
+        self.open_age.durations().iter().filter(|d| **d > age).count()
+    }
+
+    /// Open alerts with [`Severity::Critical`].
+    pub fn open_critical(&self) -> usize {
+        self.open_by_severity()
+            .get(&Some(Severity::Critical))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Open critical alerts per repository. Only alerts listed for an
+    /// organization are counted, since others don't name their repository.
+    pub fn open_critical_by_repository(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        let critical = self
+            .alerts
+            .iter()
+            .filter(|a| a.is_open() && a.severity == Some(Severity::Critical));
+        for alert in critical {
+            if let Some(repository) = &alert.repository {
+                *counts.entry(repository.as_str()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    fn dependabot(repository: &str, state: &str, severity: &str, created_at: &str) -> Value {
+        json!({
+            "number": 2,
+            "state": state,
+            "dependency": {
+                "package": {"ecosystem": "pip", "name": "django"},
+                "manifest_path": "path/to/requirements.txt",
+                "scope": "runtime"
+            },
+            "security_advisory": {
+                "ghsa_id": "GHSA-rf4j-j272-fj86",
+                "cve_id": "CVE-2018-6188",
+                "summary": "Django allows remote attackers to obtain potentially sensitive information",
+                "severity": severity
+            },
+            "url": "https://api.github.com/repos/octocat/hello-world/dependabot/alerts/2",
+            "html_url": "https://github.com/octocat/hello-world/security/dependabot/2",
+            "created_at": created_at,
+            "updated_at": created_at,
+            "dismissed_at": null,
+            "fixed_at": null,
+            "repository": {"full_name": repository}
+        })
+    }
+
+    #[test]
+    fn built_alerts() {
+        assert_eq!(
+            "https://api.github.com/repos/octocat/Hello-World/dependabot/alerts?severity=high%2Ccritical&per_page=100",
+            repo(json!({}))
+                .dependabot_alerts()
+                .severities(&[Severity::High, Severity::Critical])
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn alert_rollup() {
+        let dependabot: Vec<DependabotAlert> = vec![
+            dependabot("octocat/Hello-World", "open", "critical", "2020-01-01T00:00:00Z"),
+            dependabot("octocat/Hello-World", "open", "critical", "2020-03-01T00:00:00Z"),
+            dependabot("octocat/Spoon-Knife", "open", "low", "2020-03-20T00:00:00Z"),
+            dependabot("octocat/Spoon-Knife", "fixed", "critical", "2020-01-01T00:00:00Z"),
+        ]
+        .into_iter()
+        .map(|a| serde_json::from_value(a).unwrap())
+        .collect();
+        let secret: SecretScanningAlert = serde_json::from_value(json!({
+            "number": 42,
+            "state": "open",
+            "resolution": null,
+            "secret_type": "mailchimp_api_key",
+            "secret_type_display_name": "Mailchimp API Key",
+            "url": "https://api.github.com/repos/octocat/Hello-World/secret-scanning/alerts/42",
+            "html_url": "https://github.com/octocat/Hello-World/security/secret-scanning/42",
+            "created_at": "2020-03-30T00:00:00Z",
+            "resolved_at": null
+        }))
+        .unwrap();
+        let now = "2020-04-01T00:00:00Z".parse().unwrap();
+        let summaries = dependabot.iter().map(DependabotAlert::summary);
+        let rollup = AlertRollup::new(summaries.chain(Some(secret.summary())), &now);
+
+        assert_eq!(4, rollup.open());
+        assert_eq!(2, rollup.open_critical());
+        assert_eq!(Some(&1), rollup.open_by_severity().get(&None));
+        assert_eq!(Some(&1), rollup.by_state().get("fixed"));
+        assert_eq!(Some(&1), rollup.by_kind().get(&AlertKind::SecretScanning));
+        assert_eq!(2, rollup.open_older_than(Duration::days(30)));
+        assert_eq!(
+            vec![("octocat/Hello-World", 2)],
+            rollup.open_critical_by_repository().into_iter().collect::<Vec<_>>(),
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! For getting commit statuses and check runs, and whether a ref is green.
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{Conclusion, Status};
+use crate::{Repo, Result};
+
+/// The statuses of a ref, combined into a single state.
+///
+/// Only covers the commit status API. Check runs, which [Github Actions]
+/// reports, are listed separately with
+/// [`Repo::check_runs`](crate::Repo::check_runs).
+///
+/// [Github Actions]: https://docs.github.com/en/actions
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CombinedStatus {
+    /// `pending` if there are no statuses.
+    state: StatusState,
+    sha: String,
+    total_count: u64,
+    statuses: Vec<CommitStatus>,
+}
+
+/// The latest status of a commit for a single context.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommitStatus {
+    id: u64,
+    node_id: String,
+    url: String,
+    state: StatusState,
+    /// Such as `continuous-integration/jenkins`.
+    context: String,
+    description: Option<String>,
+    target_url: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusState {
+    Success,
+    Pending,
+    Failure,
+    Error,
+}
+
+/// A check run, such as a [Github Actions] job.
+///
+/// [Github Actions]: https://docs.github.com/en/actions
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheckRun {
+    id: u64,
+    node_id: String,
+    head_sha: String,
+    name: String,
+    status: Status,
+    /// `None` until the check run is completed.
+    conclusion: Option<Conclusion>,
+    started_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    url: String,
+    html_url: Option<String>,
+    details_url: Option<String>,
+    /// `None` if the app was deleted.
+    app: Option<App>,
+    check_suite: Option<CheckSuiteId>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CheckSuiteId {
+    id: u64,
+}
+
+/// A group of check runs created by one [`App`] for one commit.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CheckSuite {
+    id: u64,
+    node_id: String,
+    head_branch: Option<String>,
+    head_sha: String,
+    status: Option<Status>,
+    /// `None` until every check run in the suite is completed.
+    conclusion: Option<Conclusion>,
+    /// `None` if the app was deleted.
+    app: Option<App>,
+    latest_check_runs_count: u64,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    url: Option<String>,
+}
+
+/// A [Github App] that creates check runs, such as [Github Actions].
+///
+/// [Github App]: https://docs.github.com/en/apps
+/// [Github Actions]: https://docs.github.com/en/actions
+#[derive(Debug, Deserialize, Serialize)]
+pub struct App {
+    id: u64,
+    slug: Option<String>,
+    name: String,
+    html_url: String,
+}
+
+impl CombinedStatus {
+    /// `pending` if there are no statuses.
+    pub fn state(&self) -> StatusState {
+        self.state
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn total_count(&self) -> u64 {
+        self.total_count
+    }
+
+    pub fn statuses(&self) -> &[CommitStatus] {
+        &self.statuses
+    }
+}
+
+impl CommitStatus {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> StatusState {
+        self.state
+    }
+
+    /// Such as `continuous-integration/jenkins`.
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn target_url(&self) -> &Option<String> {
+        &self.target_url
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
+
+impl CheckRun {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn head_sha(&self) -> &str {
+        &self.head_sha
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// `None` until the check run is completed.
+    pub fn conclusion(&self) -> Option<Conclusion> {
+        self.conclusion
+    }
+
+    pub fn started_at(&self) -> &Option<DateTime<Utc>> {
+        &self.started_at
+    }
+
+    pub fn completed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.completed_at
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &Option<String> {
+        &self.html_url
+    }
+
+    pub fn details_url(&self) -> &Option<String> {
+        &self.details_url
+    }
+
+    /// `None` if the app was deleted.
+    pub fn app(&self) -> &Option<App> {
+        &self.app
+    }
+
+    /// ID of the [`CheckSuite`] that the check run belongs to.
+    pub fn check_suite_id(&self) -> Option<u64> {
+        self.check_suite.as_ref().map(|s| s.id)
+    }
+}
+
+impl CheckSuite {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn head_branch(&self) -> &Option<String> {
+        &self.head_branch
+    }
+
+    pub fn head_sha(&self) -> &str {
+        &self.head_sha
+    }
+
+    pub fn status(&self) -> Option<Status> {
+        self.status
+    }
+
+    /// `None` until every check run in the suite is completed.
+    pub fn conclusion(&self) -> Option<Conclusion> {
+        self.conclusion
+    }
+
+    /// `None` if the app was deleted.
+    pub fn app(&self) -> &Option<App> {
+        &self.app
+    }
+
+    pub fn latest_check_runs_count(&self) -> u64 {
+        self.latest_check_runs_count
+    }
+
+    pub fn created_at(&self) -> &Option<DateTime<Utc>> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &Option<DateTime<Utc>> {
+        &self.updated_at
+    }
+
+    pub fn url(&self) -> &Option<String> {
+        &self.url
+    }
+}
+
+impl App {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Such as `github-actions`.
+    pub fn slug(&self) -> &Option<String> {
+        &self.slug
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+}
+
+/// Whether every status and check run of a ref passed.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::Repo;
+/// use github_stats::check::RefHealth;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let health = RefHealth::default_branch(&repo, "<my user agent>", None).await?;
+///
+/// if !health.is_green() {
+///     eprintln!("Failing: {:?}", health.failing());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RefHealth {
+    status: CombinedStatus,
+    check_runs: Vec<CheckRun>,
+}
+
+impl RefHealth {
+    pub fn new(status: CombinedStatus, check_runs: Vec<CheckRun>) -> Self {
+        RefHealth { status, check_runs }
+    }
+
+    /// Gets the combined status and check runs of `r#ref`, which can be a
+    /// branch, tag or SHA.
+    pub async fn fetch(
+        repo: &Repo,
+        r#ref: &str,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let status = repo
+            .combined_status(r#ref)
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        let check_runs = repo
+            .check_runs(r#ref)
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        Ok(RefHealth::new(status, check_runs))
+    }
+
+    /// Gets the health of [`Repo::default_branch`].
+    pub async fn default_branch(
+        repo: &Repo,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        RefHealth::fetch(repo, repo.default_branch(), user_agent, authorization).await
+    }
+
+    pub fn status(&self) -> &CombinedStatus {
+        &self.status
+    }
+
+    pub fn check_runs(&self) -> &[CheckRun] {
+        &self.check_runs
+    }
+
+    /// If every status succeeded and every check run completed without
+    /// failing.
+    ///
+    /// Neutral and skipped check runs count as passing. A ref without any
+    /// statuses or check runs is green.
+    pub fn is_green(&self) -> bool {
+        self.failing().is_empty() && self.pending().is_empty()
+    }
+
+    /// Contexts of failed statuses and names of failed check runs.
+    pub fn failing(&self) -> Vec<&str> {
+        let statuses = self
+            .status
+            .statuses
+            .iter()
+            .filter(|s| matches!(s.state, StatusState::Failure | StatusState::Error))
+            .map(CommitStatus::context);
+        let check_runs = self
+            .check_runs
+            .iter()
+            .filter(|c| c.conclusion.is_some_and(|c| !passed(c)))
+            .map(CheckRun::name);
+        statuses.chain(check_runs).collect()
+    }
+
+    /// Contexts of pending statuses and names of check runs that haven't
+    /// completed.
+    pub fn pending(&self) -> Vec<&str> {
+        let statuses = self
+            .status
+            .statuses
+            .iter()
+            .filter(|s| s.state == StatusState::Pending)
+            .map(CommitStatus::context);
+        let check_runs = self
+            .check_runs
+            .iter()
+            .filter(|c| c.conclusion.is_none())
+            .map(CheckRun::name);
+        statuses.chain(check_runs).collect()
+    }
+}
+
+fn passed(conclusion: Conclusion) -> bool {
+    use Conclusion::*;
+    matches!(conclusion, Success | Neutral | Skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    const SHA: &str = "6dcb09b5b57875f334f61aebed695e2e4193db5e";
+
+    fn status(context: &str, state: &str) -> Value {
+        json!({
+            "id": 1,
+            "node_id": "MDY6U3RhdHVzMQ==",
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/statuses/{}", SHA),
+            "state": state,
+            "context": context,
+            "description": "Build has completed successfully",
+            "target_url": "https://ci.example.com/1000/output",
+            "created_at": "2012-07-20T01:19:13Z",
+            "updated_at": "2012-07-20T01:19:13Z"
+        })
+    }
+
+    fn check_run(name: &str, status: &str, conclusion: Option<&str>) -> Value {
+        json!({
+            "id": 4,
+            "node_id": "MDg6Q2hlY2tSdW40",
+            "head_sha": SHA,
+            "name": name,
+            "status": status,
+            "conclusion": conclusion,
+            "started_at": "2018-05-04T01:14:52Z",
+            "completed_at": null,
+            "url": "https://api.github.com/repos/octocat/Hello-World/check-runs/4",
+            "html_url": "https://github.com/octocat/Hello-World/runs/4",
+            "details_url": "https://example.com",
+            "app": {
+                "id": 15368,
+                "slug": "github-actions",
+                "name": "GitHub Actions",
+                "html_url": "https://github.com/apps/github-actions"
+            },
+            "check_suite": {"id": 5}
+        })
+    }
+
+    fn health(statuses: Vec<Value>, check_runs: Vec<Value>) -> RefHealth {
+        let status = serde_json::from_value(json!({
+            "state": "success",
+            "sha": SHA,
+            "total_count": statuses.len(),
+            "statuses": statuses
+        }))
+        .unwrap();
+        let check_runs = check_runs
+            .into_iter()
+            .map(|c| serde_json::from_value(c).unwrap())
+            .collect();
+        RefHealth::new(status, check_runs)
+    }
+
+    #[test]
+    fn built_check_runs() {
+        assert_eq!(
+            "https://api.github.com/repos/octocat/Hello-World/commits/master/check-runs?per_page=100",
+            repo(json!({})).check_runs("master").to_string(),
+        );
+    }
+
+    #[test]
+    fn ref_health() {
+        let green = health(
+            vec![status("ci/jenkins", "success")],
+            vec![check_run("test", "completed", Some("success")), check_run("docs", "completed", Some("skipped"))],
+        );
+        let red = health(
+            vec![status("ci/jenkins", "error"), status("ci/coverage", "pending")],
+            vec![check_run("test", "completed", Some("timed_out")), check_run("lint", "in_progress", None)],
+        );
+
+        assert!(green.is_green());
+        assert!(health(vec![], vec![]).is_green());
+        assert!(!red.is_green());
+        assert_eq!(vec!["ci/jenkins", "test"], red.failing());
+        assert_eq!(vec!["ci/coverage", "lint"], red.pending());
+        assert_eq!(Some(5), red.check_runs()[0].check_suite_id());
+    }
+}
@@ -0,0 +1,325 @@
+//! For finding who owns the files of a repository, from its `CODEOWNERS`.
+
+use std::collections::BTreeMap;
+
+use crate::tree::Tree;
+use crate::{glob, Repo, Result};
+
+/// Places [Github] looks for a `CODEOWNERS` file, in order.
+///
+/// [Github]: https://github.com/
+const CODEOWNERS_PATHS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// The rules of a `CODEOWNERS` file.
+#[derive(Debug)]
+pub struct CodeOwners {
+    path: String,
+    rules: Vec<Rule>,
+    invalid_lines: Vec<usize>,
+}
+
+/// A line of a `CODEOWNERS` file.
+#[derive(Debug)]
+pub struct Rule {
+    /// Starting from `1`.
+    line: usize,
+    pattern: String,
+    /// Such as `@octocat`, `@org/team` or an email address.
+    owners: Vec<String>,
+    /// The pattern as matched against full paths.
+    glob: String,
+    /// If the pattern only matches directories, such as `docs/`.
+    directory: bool,
+    /// If matching a directory matches everything in it. Only `dir/*` doesn't.
+    descendants: bool,
+}
+
+impl CodeOwners {
+    /// Parses the `CODEOWNERS` file found at `path`.
+    ///
+    /// Lines [Github] would reject, such as negated patterns, are skipped and
+    /// listed in [`CodeOwners::invalid_lines`].
+    ///
+    /// [Github]: https://github.com/
+    pub fn parse(path: &str, text: &str) -> Self {
+        let mut rules = Vec::new();
+        let mut invalid_lines = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            let pattern = match tokens.next() {
+                Some(p) if !p.starts_with('#') => p,
+                _ => continue,
+            };
+            let owners = tokens.take_while(|t| !t.starts_with('#')).map(String::from).collect();
+            match Rule::new(i + 1, pattern, owners) {
+                Some(rule) => rules.push(rule),
+                None => invalid_lines.push(i + 1),
+            }
+        }
+        CodeOwners {
+            path: String::from(path),
+            rules,
+            invalid_lines,
+        }
+    }
+
+    /// Gets the `CODEOWNERS` file of `repo` at `r#ref`, looking in `.github`,
+    /// the root and `docs` like [Github] does.
+    ///
+    /// `None` if there isn't one. Makes up to three requests.
+    ///
+    /// [Github]: https://github.com/
+    pub async fn fetch(
+        repo: &Repo,
+        r#ref: &str,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Option<Self>> {
+        for path in CODEOWNERS_PATHS.iter() {
+            let file = repo
+                .raw_file(path, r#ref)
+                .authorized(authorization)
+                .fetch_optional(user_agent)
+                .await?;
+            if let Some(file) = file {
+                return Ok(Some(CodeOwners::parse(path, file.text()?)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Where the file was found, such as `.github/CODEOWNERS`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Line numbers of the rules that were skipped, starting from `1`.
+    pub fn invalid_lines(&self) -> &[usize] {
+        &self.invalid_lines
+    }
+
+    /// The last rule matching `path`, which is the one that applies.
+    pub fn rule(&self, path: &str) -> Option<&Rule> {
+        self.rules.iter().rev().find(|r| r.matches(path))
+    }
+
+    /// Owners of `path`. Empty if no rule matches, or if the rule that
+    /// applies has no owners.
+    pub fn owners(&self, path: &str) -> &[String] {
+        self.rule(path).map_or(&[], |r| &r.owners)
+    }
+
+    /// Owners of each file in `tree`, by path.
+    pub fn resolve<'a>(&'a self, tree: &'a Tree) -> BTreeMap<&'a str, &'a [String]> {
+        tree.files().map(|f| (f.path(), self.owners(f.path()))).collect()
+    }
+}
+
+impl Rule {
+    fn new(line: usize, pattern: &str, owners: Vec<String>) -> Option<Self> {
+        // Github supports neither negation, character ranges nor escaping a
+        // `#` with `\`.
+        if pattern.starts_with(['!', '\\']) || pattern.contains(['[', ']']) {
+            return None;
+        }
+        let directory = pattern.ends_with('/');
+        let trimmed = pattern.trim_end_matches('/');
+        // Patterns with a `/` anywhere but the end are relative to the root,
+        // others match at any depth.
+        let glob = match trimmed.strip_prefix('/') {
+            Some(anchored) => String::from(anchored),
+            None if trimmed.contains('/') => String::from(trimmed),
+            None => format!("**/{}", trimmed),
+        };
+        if glob.is_empty() || glob == "**/" {
+            return None;
+        }
+        let descendants = directory || !glob.ends_with("/*") && glob != "*";
+        Some(Rule {
+            line,
+            pattern: String::from(pattern),
+            owners,
+            glob,
+            directory,
+            descendants,
+        })
+    }
+
+    /// Starting from `1`.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Such as `@octocat`, `@org/team` or an email address.
+    pub fn owners(&self) -> &[String] {
+        &self.owners
+    }
+
+    /// If the rule applies to the file at `path`, either directly or
+    /// through one of its directories.
+    pub fn matches(&self, path: &str) -> bool {
+        if !self.directory && glob::matches_path(&self.glob, path) {
+            return true;
+        }
+        self.descendants
+            && path
+                .match_indices('/')
+                .any(|(i, _)| glob::matches_path(&self.glob, &path[..i]))
+    }
+}
+
+/// How many files of a tree have an owner.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::Repo;
+/// use github_stats::codeowners::OwnershipCoverage;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let coverage = OwnershipCoverage::fetch(&repo, repo.default_branch(), "<my user agent>", None).await?;
+///
+/// println!("{:.1}% of files have no owner", coverage.unowned_percentage());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct OwnershipCoverage {
+    files: usize,
+    unowned: Vec<String>,
+    files_per_owner: BTreeMap<String, usize>,
+}
+
+impl OwnershipCoverage {
+    /// Resolves the owners of every file in `tree`. With no `codeowners`,
+    /// every file is unowned.
+    pub fn new(codeowners: Option<&CodeOwners>, tree: &Tree) -> Self {
+        let mut unowned = Vec::new();
+        let mut files_per_owner = BTreeMap::new();
+        let mut files = 0;
+        for file in tree.files() {
+            files += 1;
+            let owners = codeowners.map_or(&[][..], |c| c.owners(file.path()));
+            if owners.is_empty() {
+                unowned.push(String::from(file.path()));
+            }
+            for owner in owners {
+                *files_per_owner.entry(owner.clone()).or_insert(0) += 1;
+            }
+        }
+        OwnershipCoverage {
+            files,
+            unowned,
+            files_per_owner,
+        }
+    }
+
+    /// Gets the `CODEOWNERS` file and the complete tree of `repo` at `r#ref`,
+    /// and resolves the owners of every file.
+    pub async fn fetch(
+        repo: &Repo,
+        r#ref: &str,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let codeowners = CodeOwners::fetch(repo, r#ref, user_agent, authorization).await?;
+        let tree = Tree::fetch_complete(repo, r#ref, user_agent, authorization).await?;
+        Ok(OwnershipCoverage::new(codeowners.as_ref(), &tree))
+    }
+
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    /// Paths of the files without an owner.
+    pub fn unowned(&self) -> &[String] {
+        &self.unowned
+    }
+
+    /// From `0` to `100`. `0` for a tree without files.
+    pub fn unowned_percentage(&self) -> f64 {
+        if self.files == 0 {
+            return 0.0;
+        }
+        self.unowned.len() as f64 / self.files as f64 * 100.0
+    }
+
+    /// Number of files each owner covers.
+    pub fn files_per_owner(&self) -> &BTreeMap<String, usize> {
+        &self.files_per_owner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*       @octocat
+
+*.js    @js-owner # inline comment
+/build/logs/ @doctocat
+docs/*  docs@example.com
+apps/   @octocat/apps
+/scripts/ @doctocat @octocat
+/scripts/generated
+!negated @nobody
+\\#file @nobody
+";
+
+    #[test]
+    fn github_semantics() {
+        let codeowners = CodeOwners::parse(".github/CODEOWNERS", CODEOWNERS);
+
+        assert_eq!(&[10, 11], codeowners.invalid_lines());
+        assert_eq!(["@octocat"], codeowners.owners("#file"));
+        assert_eq!(["@octocat"], codeowners.owners("README.md"));
+        assert_eq!(["@js-owner"], codeowners.owners("src/app/index.js"));
+        assert_eq!(["@doctocat"], codeowners.owners("build/logs/2020/out.log"));
+        assert_eq!(["docs@example.com"], codeowners.owners("docs/setup.md"));
+        assert_eq!(["@octocat"], codeowners.owners("docs/guides/setup.md"));
+        assert_eq!(["@octocat/apps"], codeowners.owners("web/apps/main.rs"));
+        assert_eq!(2, codeowners.owners("scripts/deploy.sh").len());
+        assert!(codeowners.owners("scripts/generated/types.rs").is_empty());
+    }
+
+    #[test]
+    fn ownership_coverage() {
+        let entries: Vec<_> = ["README.md", "src/lib.rs", "src/main.rs", "docs/setup.md"]
+            .iter()
+            .map(|path| json!({
+                "path": path,
+                "mode": "100644",
+                "type": "blob",
+                "sha": "7c258a9869f33c1e1e1f74fbb32f07c86cb5a75b",
+                "size": 100
+            }))
+            .collect();
+        let tree: Tree = serde_json::from_value(json!({
+            "sha": "9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "url": "https://api.github.com/repos/octocat/Hello-World/trees/9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "tree": entries,
+            "truncated": false
+        }))
+        .unwrap();
+        let codeowners = CodeOwners::parse("CODEOWNERS", "/src/ @octocat\n");
+        let coverage = OwnershipCoverage::new(Some(&codeowners), &tree);
+
+        assert_eq!(vec!["README.md", "docs/setup.md"], coverage.unowned());
+        assert_eq!(50.0, coverage.unowned_percentage());
+        assert_eq!(Some(&2), coverage.files_per_owner().get("@octocat"));
+        assert_eq!(100.0, OwnershipCoverage::new(None, &tree).unowned_percentage());
+    }
+}
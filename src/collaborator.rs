@@ -0,0 +1,398 @@
+//! For reviewing who can access a repository, and how.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::request::List;
+use crate::{org, Repo, Result, User};
+
+/// A user who can access a repository.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Collaborator {
+    #[serde(flatten)]
+    user: User,
+    permissions: Permissions,
+    /// Such as `admin`, or the name of a custom role.
+    #[serde(default)]
+    role_name: Option<String>,
+}
+
+/// What a collaborator is allowed to do.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Permissions {
+    pull: bool,
+    #[serde(default)]
+    triage: bool,
+    push: bool,
+    #[serde(default)]
+    maintain: bool,
+    admin: bool,
+}
+
+/// A team that can access a repository.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Team {
+    id: u64,
+    node_id: String,
+    url: String,
+    html_url: String,
+    name: String,
+    slug: String,
+    description: Option<String>,
+    /// `closed` or `secret`.
+    #[serde(default)]
+    privacy: Option<String>,
+    /// The team's permission on the repository.
+    permission: Permission,
+}
+
+/// A permission level, from least to most access.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    Pull,
+    Triage,
+    Push,
+    Maintain,
+    Admin,
+}
+
+/// How a collaborator came to access a repository.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Affiliation {
+    /// Not members of the organization.
+    Outside,
+    /// Added to the repository, rather than through a team or organization.
+    Direct,
+    All,
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Permission::*;
+        write!(f, "{}", match self {
+            Pull => "pull",
+            Triage => "triage",
+            Push => "push",
+            Maintain => "maintain",
+            Admin => "admin",
+        })
+    }
+}
+
+impl fmt::Display for Affiliation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Affiliation::*;
+        write!(f, "{}", match self {
+            Outside => "outside",
+            Direct => "direct",
+            All => "all",
+        })
+    }
+}
+
+impl Collaborator {
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn login(&self) -> &str {
+        self.user.login()
+    }
+
+    pub fn permissions(&self) -> Permissions {
+        self.permissions
+    }
+
+    /// Such as `admin`, or the name of a custom role.
+    pub fn role_name(&self) -> &Option<String> {
+        &self.role_name
+    }
+}
+
+impl Permissions {
+    pub fn pull(&self) -> bool {
+        self.pull
+    }
+
+    pub fn triage(&self) -> bool {
+        self.triage
+    }
+
+    pub fn push(&self) -> bool {
+        self.push
+    }
+
+    pub fn maintain(&self) -> bool {
+        self.maintain
+    }
+
+    pub fn admin(&self) -> bool {
+        self.admin
+    }
+
+    /// The highest permission granted. `None` if not even `pull` is.
+    pub fn level(&self) -> Option<Permission> {
+        use Permission::*;
+        [
+            (self.admin, Admin),
+            (self.maintain, Maintain),
+            (self.push, Push),
+            (self.triage, Triage),
+            (self.pull, Pull),
+        ]
+        .iter()
+        .find(|(granted, _)| *granted)
+        .map(|(_, level)| *level)
+    }
+}
+
+impl Team {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    /// `closed` or `secret`.
+    pub fn privacy(&self) -> &Option<String> {
+        &self.privacy
+    }
+
+    /// The team's permission on the repository.
+    pub fn permission(&self) -> Permission {
+        self.permission
+    }
+}
+
+/// Filters for [`Repo::collaborators`](crate::Repo::collaborators).
+impl List<Collaborator> {
+    /// Defaults to [`Affiliation::All`].
+    pub fn affiliation(self, affiliation: Affiliation) -> Self {
+        self.param("affiliation", affiliation)
+    }
+
+    /// Only collaborators with exactly this permission.
+    pub fn permission(self, permission: Permission) -> Self {
+        self.param("permission", permission)
+    }
+}
+
+/// Who has admin access to a repository, and which outside collaborators
+/// can write to it.
+#[derive(Debug)]
+pub struct AccessReport {
+    full_name: String,
+    collaborators: Vec<Collaborator>,
+    outside_collaborators: Vec<Collaborator>,
+    teams: Vec<Team>,
+}
+
+impl AccessReport {
+    pub fn new(
+        full_name: &str,
+        collaborators: Vec<Collaborator>,
+        outside_collaborators: Vec<Collaborator>,
+        teams: Vec<Team>,
+    ) -> Self {
+        AccessReport {
+            full_name: String::from(full_name),
+            collaborators,
+            outside_collaborators,
+            teams,
+        }
+    }
+
+    /// Lists the collaborators, outside collaborators and teams of `repo`.
+    ///
+    /// Needs an authorization token with push access to `repo`.
+    pub async fn fetch(repo: &Repo, user_agent: &str, authorization: Option<&str>) -> Result<Self> {
+        let collaborators = repo
+            .collaborators()
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        let outside_collaborators = repo
+            .collaborators()
+            .affiliation(Affiliation::Outside)
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        let teams = repo
+            .teams()
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        Ok(AccessReport::new(repo.full_name(), collaborators, outside_collaborators, teams))
+    }
+
+    /// Such as `octocat/Hello-World`.
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    pub fn collaborators(&self) -> &[Collaborator] {
+        &self.collaborators
+    }
+
+    pub fn outside_collaborators(&self) -> &[Collaborator] {
+        &self.outside_collaborators
+    }
+
+    pub fn teams(&self) -> &[Team] {
+        &self.teams
+    }
+
+    /// Logins of the collaborators with admin access.
+    pub fn admins(&self) -> Vec<&str> {
+        self.collaborators
+            .iter()
+            .filter(|c| c.permissions.admin)
+            .map(Collaborator::login)
+            .collect()
+    }
+
+    /// Teams with admin access.
+    pub fn admin_teams(&self) -> Vec<&Team> {
+        self.teams
+            .iter()
+            .filter(|t| t.permission == Permission::Admin)
+            .collect()
+    }
+
+    /// Logins of the outside collaborators who can push, or more.
+    pub fn outside_writers(&self) -> Vec<&str> {
+        self.outside_collaborators
+            .iter()
+            .filter(|c| c.permissions.push)
+            .map(Collaborator::login)
+            .collect()
+    }
+
+    /// If more than `max_admins` collaborators are admins, or any outside
+    /// collaborator can write.
+    pub fn is_flagged(&self, max_admins: usize) -> bool {
+        self.admins().len() > max_admins || !self.outside_writers().is_empty()
+    }
+}
+
+/// Gets the access report of every unarchived repository of `org`.
+///
+/// Makes three requests per repository, and needs an authorization token of
+/// an organization owner to see every repository.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::collaborator;
+///
+/// let reports = collaborator::org_access("my-org", "<my user agent>", Some("<token>")).await?;
+///
+/// for report in reports.iter().filter(|r| r.is_flagged(3)) {
+///     println!("{}: admins {:?}, outside writers {:?}",
+///         report.full_name(), report.admins(), report.outside_writers());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn org_access(
+    org: &str,
+    user_agent: &str,
+    authorization: Option<&str>,
+) -> Result<Vec<AccessReport>> {
+    let repos = org::repos(org)
+        .authorized(authorization)
+        .fetch(user_agent)
+        .await?;
+    let mut reports = Vec::new();
+    for repo in repos.iter().filter(|r| !r.archived()) {
+        reports.push(AccessReport::fetch(repo, user_agent, authorization).await?);
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::user::tests::user;
+
+    fn collaborator(login: &str, level: &str) -> Value {
+        let rank = ["pull", "triage", "push", "maintain", "admin"]
+            .iter()
+            .position(|l| *l == level)
+            .unwrap();
+        let mut collaborator = user(login);
+        collaborator["permissions"] = json!({
+            "pull": true,
+            "triage": rank >= 1,
+            "push": rank >= 2,
+            "maintain": rank >= 3,
+            "admin": rank >= 4
+        });
+        collaborator["role_name"] = json!(level);
+        collaborator
+    }
+
+    #[test]
+    fn access_report() {
+        let collaborators: Vec<Collaborator> = serde_json::from_value(json!([
+            collaborator("octocat", "admin"),
+            collaborator("hubot", "admin"),
+            collaborator("monalisa", "triage"),
+            collaborator("contractor", "push"),
+        ]))
+        .unwrap();
+        let outside: Vec<Collaborator> = serde_json::from_value(json!([
+            collaborator("contractor", "push"),
+        ]))
+        .unwrap();
+        let teams: Vec<Team> = serde_json::from_value(json!([{
+            "id": 1,
+            "node_id": "MDQ6VGVhbTE=",
+            "url": "https://api.github.com/teams/1",
+            "html_url": "https://github.com/orgs/github/teams/justice-league",
+            "name": "Justice League",
+            "slug": "justice-league",
+            "description": "A great team.",
+            "privacy": "closed",
+            "permission": "admin",
+            "members_url": "https://api.github.com/teams/1/members{/member}",
+            "repositories_url": "https://api.github.com/teams/1/repos",
+            "parent": null
+        }]))
+        .unwrap();
+        let report = AccessReport::new("octocat/Hello-World", collaborators, outside, teams);
+
+        assert_eq!(Some(Permission::Triage), report.collaborators()[2].permissions().level());
+        assert_eq!(vec!["octocat", "hubot"], report.admins());
+        assert_eq!(1, report.admin_teams().len());
+        assert_eq!(vec!["contractor"], report.outside_writers());
+        assert!(report.is_flagged(2));
+    }
+}
@@ -0,0 +1,308 @@
+//! For getting a repository's commits.
+
+use chrono::prelude::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::request::List;
+use crate::User;
+
+/// A commit as [Github] reports it.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Commit {
+    sha: String,
+    node_id: String,
+    url: String,
+    html_url: String,
+    commit: GitCommit,
+    /// `None` if the author's email isn't linked to a [Github] account.
+    ///
+    /// [Github]: https://github.com/
+    author: Option<User>,
+    /// `None` if the committer's email isn't linked to a [Github] account.
+    ///
+    /// [Github]: https://github.com/
+    committer: Option<User>,
+    parents: Vec<CommitParent>,
+}
+
+/// The git data of a [`Commit`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitCommit {
+    author: Option<GitActor>,
+    committer: Option<GitActor>,
+    message: String,
+    comment_count: u64,
+}
+
+/// The author or committer recorded in a git commit.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitActor {
+    name: String,
+    email: String,
+    date: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommitParent {
+    sha: String,
+    url: String,
+}
+
+/// A file changed by a commit or between two refs.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangedFile {
+    /// `None` for some changes, such as to submodules.
+    sha: Option<String>,
+    filename: String,
+    status: FileStatus,
+    additions: u64,
+    deletions: u64,
+    /// `additions + deletions`
+    changes: u64,
+    blob_url: String,
+    raw_url: String,
+    contents_url: String,
+    /// Not included for binary or very large diffs.
+    patch: Option<String>,
+    /// Only included for renamed files.
+    previous_filename: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+    Renamed,
+    Copied,
+    Changed,
+    Unchanged,
+}
+
+impl Commit {
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn commit(&self) -> &GitCommit {
+        &self.commit
+    }
+
+    /// `None` if the author's email isn't linked to a [Github] account.
+    ///
+    /// [Github]: https://github.com/
+    pub fn author(&self) -> &Option<User> {
+        &self.author
+    }
+
+    /// `None` if the committer's email isn't linked to a [Github] account.
+    ///
+    /// [Github]: https://github.com/
+    pub fn committer(&self) -> &Option<User> {
+        &self.committer
+    }
+
+    pub fn parents(&self) -> &[CommitParent] {
+        &self.parents
+    }
+}
+
+impl GitCommit {
+    pub fn author(&self) -> &Option<GitActor> {
+        &self.author
+    }
+
+    pub fn committer(&self) -> &Option<GitActor> {
+        &self.committer
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn comment_count(&self) -> u64 {
+        self.comment_count
+    }
+}
+
+impl GitActor {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn date(&self) -> &DateTime<Utc> {
+        &self.date
+    }
+}
+
+impl CommitParent {
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl ChangedFile {
+    /// `None` for some changes, such as to submodules.
+    pub fn sha(&self) -> &Option<String> {
+        &self.sha
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn status(&self) -> FileStatus {
+        self.status
+    }
+
+    pub fn additions(&self) -> u64 {
+        self.additions
+    }
+
+    pub fn deletions(&self) -> u64 {
+        self.deletions
+    }
+
+    /// `additions + deletions`
+    pub fn changes(&self) -> u64 {
+        self.changes
+    }
+
+    pub fn blob_url(&self) -> &str {
+        &self.blob_url
+    }
+
+    pub fn raw_url(&self) -> &str {
+        &self.raw_url
+    }
+
+    pub fn contents_url(&self) -> &str {
+        &self.contents_url
+    }
+
+    /// Not included for binary or very large diffs.
+    pub fn patch(&self) -> &Option<String> {
+        &self.patch
+    }
+
+    /// Only included for renamed files.
+    pub fn previous_filename(&self) -> &Option<String> {
+        &self.previous_filename
+    }
+}
+
+/// Filters for [`Repo::commits`](crate::Repo::commits).
+impl List<Commit> {
+    /// Lists commits starting from this SHA or branch.
+    ///
+    /// Defaults to the repository's default branch.
+    pub fn sha(self, sha: &str) -> Self {
+        self.param("sha", sha)
+    }
+
+    /// Only commits containing this file path.
+    pub fn path(self, path: &str) -> Self {
+        self.param("path", path)
+    }
+
+    /// Only commits by this author's [Github] login or email address.
+    ///
+    /// [Github]: https://github.com/
+    pub fn author(self, author: &str) -> Self {
+        self.param("author", author)
+    }
+
+    /// Only commits by this committer's [Github] login or email address.
+    ///
+    /// [Github]: https://github.com/
+    pub fn committer(self, committer: &str) -> Self {
+        self.param("committer", committer)
+    }
+
+    /// Only commits last updated at or after this time.
+    pub fn since(self, since: &DateTime<Utc>) -> Self {
+        self.param("since", since.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+
+    /// Only commits last updated at or before this time.
+    pub fn until(self, until: &DateTime<Utc>) -> Self {
+        self.param("until", until.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    /// A commit whose author isn't linked to a [Github] account.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn commit(sha: &str) -> Value {
+        json!({
+            "sha": sha,
+            "node_id": "MDY6Q29tbWl0NmRjYjA5YjViNTc4NzVmMzM0ZjYxYWViZWQ2OTVlMmU0MTkzZGI1ZQ==",
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/commits/{}", sha),
+            "html_url": format!("https://github.com/octocat/Hello-World/commit/{}", sha),
+            "commit": {
+                "author": {"name": "Monalisa Octocat", "email": "support@github.com", "date": "2011-04-14T16:00:49Z"},
+                "committer": {"name": "Monalisa Octocat", "email": "support@github.com", "date": "2011-04-14T16:00:49Z"},
+                "message": "Fix all the bugs",
+                "comment_count": 0
+            },
+            "author": null,
+            "committer": null,
+            "parents": [{
+                "sha": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d",
+                "url": "https://api.github.com/repos/octocat/Hello-World/commits/7fd1a60b01f91b314f59955a4e4d4e80d8edf11d",
+                "html_url": "https://github.com/octocat/Hello-World/commit/7fd1a60b01f91b314f59955a4e4d4e80d8edf11d"
+            }]
+        })
+    }
+
+    #[test]
+    fn built_commits() {
+        const EXPECTED: &str = "https://api.github.com/repos/octocat/Hello-World/commits?sha=develop&author=octocat&since=2020-01-01T00%3A00%3A00Z&per_page=100";
+        let since = "2020-01-01T00:00:00Z".parse().unwrap();
+        let commits = repo(json!({}))
+            .commits()
+            .sha("develop")
+            .author("octocat")
+            .since(&since);
+
+        assert_eq!(EXPECTED, commits.to_string());
+    }
+
+    #[test]
+    fn unlinked_author() {
+        let commit: Commit = serde_json::from_value(commit("6dcb09b5b57875f334f61aebed695e2e4193db5e")).unwrap();
+
+        assert!(commit.author().is_none());
+        assert_eq!("Monalisa Octocat", commit.commit().author().as_ref().unwrap().name());
+    }
+}
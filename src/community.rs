@@ -0,0 +1,426 @@
+//! For checking that repositories have the files [Github] recommends, such
+//! as a README, license and code of conduct.
+//!
+//! [Github]: https://github.com/
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{org, Repo, Result};
+
+/// Places [Github] looks for a security policy, in order.
+///
+/// [Github]: https://github.com/
+const SECURITY_POLICY_PATHS: [&str; 3] = ["SECURITY.md", ".github/SECURITY.md", "docs/SECURITY.md"];
+
+/// [Github]'s community profile of a public repository.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommunityProfile {
+    /// From `0` to `100`.
+    health_percentage: u64,
+    description: Option<String>,
+    documentation: Option<String>,
+    files: CommunityFiles,
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    content_reports_enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CommunityFiles {
+    #[serde(default)]
+    code_of_conduct: Option<CodeOfConduct>,
+    #[serde(default)]
+    code_of_conduct_file: Option<CommunityFile>,
+    #[serde(default)]
+    contributing: Option<CommunityFile>,
+    #[serde(default)]
+    issue_template: Option<CommunityFile>,
+    #[serde(default)]
+    pull_request_template: Option<CommunityFile>,
+    #[serde(default)]
+    license: Option<License>,
+    #[serde(default)]
+    readme: Option<CommunityFile>,
+}
+
+/// A file found by the community profile.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CommunityFile {
+    url: String,
+    html_url: Option<String>,
+}
+
+/// A code of conduct that [Github] recognized.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodeOfConduct {
+    /// Such as `contributor_covenant`.
+    key: String,
+    name: String,
+    url: Option<String>,
+    html_url: Option<String>,
+}
+
+/// A license that [Github] recognized.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct License {
+    /// Such as `mit`.
+    key: String,
+    name: String,
+    /// Such as `MIT`, or `NOASSERTION` if the license wasn't recognized.
+    spdx_id: Option<String>,
+    url: Option<String>,
+    html_url: Option<String>,
+}
+
+/// A file that makes a repository healthy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HealthFile {
+    Readme,
+    License,
+    CodeOfConduct,
+    Contributing,
+    IssueTemplate,
+    PullRequestTemplate,
+    SecurityPolicy,
+}
+
+impl HealthFile {
+    pub const ALL: [HealthFile; 7] = [
+        HealthFile::Readme,
+        HealthFile::License,
+        HealthFile::CodeOfConduct,
+        HealthFile::Contributing,
+        HealthFile::IssueTemplate,
+        HealthFile::PullRequestTemplate,
+        HealthFile::SecurityPolicy,
+    ];
+}
+
+impl fmt::Display for HealthFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use HealthFile::*;
+        write!(f, "{}", match self {
+            Readme => "README",
+            License => "LICENSE",
+            CodeOfConduct => "CODE_OF_CONDUCT",
+            Contributing => "CONTRIBUTING",
+            IssueTemplate => "issue template",
+            PullRequestTemplate => "pull request template",
+            SecurityPolicy => "SECURITY.md",
+        })
+    }
+}
+
+impl CommunityProfile {
+    /// From `0` to `100`.
+    pub fn health_percentage(&self) -> u64 {
+        self.health_percentage
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn documentation(&self) -> &Option<String> {
+        &self.documentation
+    }
+
+    pub fn updated_at(&self) -> &Option<DateTime<Utc>> {
+        &self.updated_at
+    }
+
+    pub fn content_reports_enabled(&self) -> &Option<bool> {
+        &self.content_reports_enabled
+    }
+
+    pub fn readme(&self) -> &Option<CommunityFile> {
+        &self.files.readme
+    }
+
+    pub fn license(&self) -> &Option<License> {
+        &self.files.license
+    }
+
+    /// Only set if [Github] recognized the code of conduct. See
+    /// [`CommunityProfile::code_of_conduct_file`] otherwise.
+    ///
+    /// [Github]: https://github.com/
+    pub fn code_of_conduct(&self) -> &Option<CodeOfConduct> {
+        &self.files.code_of_conduct
+    }
+
+    pub fn code_of_conduct_file(&self) -> &Option<CommunityFile> {
+        &self.files.code_of_conduct_file
+    }
+
+    pub fn contributing(&self) -> &Option<CommunityFile> {
+        &self.files.contributing
+    }
+
+    pub fn issue_template(&self) -> &Option<CommunityFile> {
+        &self.files.issue_template
+    }
+
+    pub fn pull_request_template(&self) -> &Option<CommunityFile> {
+        &self.files.pull_request_template
+    }
+
+    /// If the profile found `file`.
+    ///
+    /// Always `false` for [`HealthFile::SecurityPolicy`], which the profile
+    /// doesn't cover. See [`RepoHealth`].
+    pub fn has(&self, file: HealthFile) -> bool {
+        use HealthFile::*;
+        let files = &self.files;
+        match file {
+            Readme => files.readme.is_some(),
+            License => files.license.is_some(),
+            CodeOfConduct => files.code_of_conduct.is_some() || files.code_of_conduct_file.is_some(),
+            Contributing => files.contributing.is_some(),
+            IssueTemplate => files.issue_template.is_some(),
+            PullRequestTemplate => files.pull_request_template.is_some(),
+            SecurityPolicy => false,
+        }
+    }
+}
+
+impl CommunityFile {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &Option<String> {
+        &self.html_url
+    }
+}
+
+impl CodeOfConduct {
+    /// Such as `contributor_covenant`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> &Option<String> {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &Option<String> {
+        &self.html_url
+    }
+}
+
+impl License {
+    /// Such as `mit`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Such as `MIT`, or `NOASSERTION` if the license wasn't recognized.
+    pub fn spdx_id(&self) -> &Option<String> {
+        &self.spdx_id
+    }
+
+    pub fn url(&self) -> &Option<String> {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &Option<String> {
+        &self.html_url
+    }
+}
+
+/// A repository's community profile, and whether it has a security policy.
+#[derive(Debug)]
+pub struct RepoHealth {
+    full_name: String,
+    profile: CommunityProfile,
+    security_policy: bool,
+}
+
+impl RepoHealth {
+    pub fn new(full_name: &str, profile: CommunityProfile, security_policy: bool) -> Self {
+        RepoHealth {
+            full_name: String::from(full_name),
+            profile,
+            security_policy,
+        }
+    }
+
+    /// Gets the community profile of `repo`, and looks for a `SECURITY.md`
+    /// in the root, `.github` and `docs` folders.
+    ///
+    /// Makes up to four requests.
+    pub async fn fetch(repo: &Repo, user_agent: &str, authorization: Option<&str>) -> Result<Self> {
+        let profile = repo
+            .community_profile()
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        let mut security_policy = false;
+        for path in SECURITY_POLICY_PATHS.iter() {
+            let file = repo
+                .contents(path, repo.default_branch())
+                .authorized(authorization);
+            if file.exists(user_agent).await? {
+                security_policy = true;
+                break;
+            }
+        }
+        Ok(RepoHealth::new(repo.full_name(), profile, security_policy))
+    }
+
+    /// Such as `octocat/Hello-World`.
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    pub fn profile(&self) -> &CommunityProfile {
+        &self.profile
+    }
+
+    pub fn has(&self, file: HealthFile) -> bool {
+        match file {
+            HealthFile::SecurityPolicy => self.security_policy,
+            _ => self.profile.has(file),
+        }
+    }
+
+    pub fn missing(&self) -> Vec<HealthFile> {
+        HealthFile::ALL.iter().copied().filter(|f| !self.has(*f)).collect()
+    }
+}
+
+/// Gets the health of every public, unarchived repository of `org`.
+///
+/// Makes up to five requests per repository.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::community;
+///
+/// let healths = community::org_health("rust-lang", "<my user agent>", None).await?;
+///
+/// for (file, repos) in community::missing_files(&healths) {
+///     println!("Missing {}: {}", file, repos.join(", "));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn org_health(
+    org: &str,
+    user_agent: &str,
+    authorization: Option<&str>,
+) -> Result<Vec<RepoHealth>> {
+    let repos = org::repos(org)
+        .authorized(authorization)
+        .fetch(user_agent)
+        .await?;
+    let mut healths = Vec::new();
+    for repo in repos.iter().filter(|r| !r.private() && !r.archived()) {
+        healths.push(RepoHealth::fetch(repo, user_agent, authorization).await?);
+    }
+    Ok(healths)
+}
+
+/// Names of the repositories missing each file.
+///
+/// Files that no repository is missing are left out.
+pub fn missing_files(healths: &[RepoHealth]) -> BTreeMap<HealthFile, Vec<&str>> {
+    let mut missing: BTreeMap<HealthFile, Vec<&str>> = BTreeMap::new();
+    for health in healths {
+        for file in health.missing() {
+            missing.entry(file).or_default().push(health.full_name());
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn file(path: &str) -> Value {
+        json!({
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/contents/{}", path),
+            "html_url": format!("https://github.com/octocat/Hello-World/blob/master/{}", path)
+        })
+    }
+
+    fn profile(files: Value) -> CommunityProfile {
+        serde_json::from_value(json!({
+            "health_percentage": 100,
+            "description": "My first repository on GitHub!",
+            "documentation": null,
+            "files": files,
+            "updated_at": "2017-02-28T19:09:29Z",
+            "content_reports_enabled": true
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn missing_health_files() {
+        let complete = profile(json!({
+            "code_of_conduct": {
+                "key": "contributor_covenant",
+                "name": "Contributor Covenant",
+                "url": "https://api.github.com/codes_of_conduct/contributor_covenant",
+                "html_url": "https://github.com/octocat/Hello-World/blob/master/CODE_OF_CONDUCT.md"
+            },
+            "code_of_conduct_file": file("CODE_OF_CONDUCT.md"),
+            "contributing": file("CONTRIBUTING"),
+            "issue_template": file("ISSUE_TEMPLATE"),
+            "pull_request_template": file("PULL_REQUEST_TEMPLATE"),
+            "license": {
+                "key": "mit",
+                "name": "MIT License",
+                "spdx_id": "MIT",
+                "url": "https://api.github.com/licenses/mit",
+                "html_url": "https://github.com/octocat/Hello-World/blob/master/LICENSE",
+                "node_id": "MDc6TGljZW5zZW1pdA=="
+            },
+            "readme": file("README.md")
+        }));
+        let bare = profile(json!({
+            "code_of_conduct": null,
+            "contributing": null,
+            "issue_template": null,
+            "pull_request_template": null,
+            "license": null,
+            "readme": file("README.md")
+        }));
+        let healths = vec![
+            RepoHealth::new("octocat/Hello-World", complete, true),
+            RepoHealth::new("octocat/Spoon-Knife", bare, false),
+        ];
+        let missing = missing_files(&healths);
+
+        assert!(healths[0].missing().is_empty());
+        assert_eq!(Some("MIT"), healths[0].profile().license().as_ref().and_then(|l| l.spdx_id().as_deref()));
+        assert_eq!(6, healths[1].missing().len());
+        assert_eq!(None, missing.get(&HealthFile::Readme));
+        assert_eq!(Some(&vec!["octocat/Spoon-Knife"]), missing.get(&HealthFile::SecurityPolicy));
+    }
+}
@@ -0,0 +1,183 @@
+//! For comparing two refs of a repository.
+
+use serde::{Deserialize, Serialize};
+
+use crate::commit::{ChangedFile, Commit};
+
+/// How far apart two refs are, and what changed between them.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::Repo;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let comparison = repo
+///     .compare("1.0.0", repo.default_branch())
+///     .fetch("<my user agent>")
+///     .await?;
+///
+/// println!("{} commits since 1.0.0", comparison.ahead_by());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Comparison {
+    url: String,
+    html_url: String,
+    permalink_url: String,
+    diff_url: String,
+    patch_url: String,
+    base_commit: Commit,
+    merge_base_commit: Commit,
+    status: CompareStatus,
+    /// Commits in head that aren't in base.
+    ahead_by: u64,
+    /// Commits in base that aren't in head.
+    behind_by: u64,
+    total_commits: u64,
+    /// At most 250 commits, oldest first.
+    commits: Vec<Commit>,
+    /// At most 300 files.
+    #[serde(default)]
+    files: Vec<ChangedFile>,
+}
+
+/// Where head stands relative to base.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompareStatus {
+    Diverged,
+    Ahead,
+    Behind,
+    Identical,
+}
+
+impl Comparison {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn permalink_url(&self) -> &str {
+        &self.permalink_url
+    }
+
+    pub fn diff_url(&self) -> &str {
+        &self.diff_url
+    }
+
+    pub fn patch_url(&self) -> &str {
+        &self.patch_url
+    }
+
+    pub fn base_commit(&self) -> &Commit {
+        &self.base_commit
+    }
+
+    pub fn merge_base_commit(&self) -> &Commit {
+        &self.merge_base_commit
+    }
+
+    pub fn status(&self) -> CompareStatus {
+        self.status
+    }
+
+    /// Commits in head that aren't in base.
+    pub fn ahead_by(&self) -> u64 {
+        self.ahead_by
+    }
+
+    /// Commits in base that aren't in head.
+    pub fn behind_by(&self) -> u64 {
+        self.behind_by
+    }
+
+    pub fn total_commits(&self) -> u64 {
+        self.total_commits
+    }
+
+    /// At most 250 commits, oldest first.
+    pub fn commits(&self) -> &[Commit] {
+        &self.commits
+    }
+
+    /// At most 300 files.
+    pub fn files(&self) -> &[ChangedFile] {
+        &self.files
+    }
+
+    /// Lines added across [`files`](Comparison::files).
+    pub fn additions(&self) -> u64 {
+        self.files.iter().map(ChangedFile::additions).sum()
+    }
+
+    /// Lines deleted across [`files`](Comparison::files).
+    pub fn deletions(&self) -> u64 {
+        self.files.iter().map(ChangedFile::deletions).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::commit::tests::commit;
+    use crate::commit::FileStatus;
+
+    #[test]
+    fn diverged_comparison() {
+        let comparison: Comparison = serde_json::from_value(json!({
+            "url": "https://api.github.com/repos/octocat/Hello-World/compare/master...topic",
+            "html_url": "https://github.com/octocat/Hello-World/compare/master...topic",
+            "permalink_url": "https://github.com/octocat/Hello-World/compare/octocat:bbcd538c8e72b8c175046e27cc8f907076331401...octocat:0328041d1152db8ae77652d1618a02e57f745f17",
+            "diff_url": "https://github.com/octocat/Hello-World/compare/master...topic.diff",
+            "patch_url": "https://github.com/octocat/Hello-World/compare/master...topic.patch",
+            "base_commit": commit("bbcd538c8e72b8c175046e27cc8f907076331401"),
+            "merge_base_commit": commit("7fd1a60b01f91b314f59955a4e4d4e80d8edf11d"),
+            "status": "diverged",
+            "ahead_by": 1,
+            "behind_by": 2,
+            "total_commits": 1,
+            "commits": [commit("0328041d1152db8ae77652d1618a02e57f745f17")],
+            "files": [
+                {
+                    "sha": "bbcd538c8e72b8c175046e27cc8f907076331401",
+                    "filename": "file1.txt",
+                    "status": "added",
+                    "additions": 103,
+                    "deletions": 21,
+                    "changes": 124,
+                    "blob_url": "https://github.com/octocat/Hello-World/blob/6dcb09b5b57875f334f61aebed695e2e4193db5e/file1.txt",
+                    "raw_url": "https://github.com/octocat/Hello-World/raw/6dcb09b5b57875f334f61aebed695e2e4193db5e/file1.txt",
+                    "contents_url": "https://api.github.com/repos/octocat/Hello-World/contents/file1.txt?ref=6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                    "patch": "@@ -132,7 +132,7 @@ module Test @@ -1000,7 +1000,7 @@ module Test"
+                },
+                {
+                    "sha": "9c4d5d0f2a3b7f1d7c2a1e2e7e0b2d6f2d1a6e3c",
+                    "filename": "file2.txt",
+                    "status": "renamed",
+                    "additions": 1,
+                    "deletions": 1,
+                    "changes": 2,
+                    "blob_url": "https://github.com/octocat/Hello-World/blob/6dcb09b5b57875f334f61aebed695e2e4193db5e/file2.txt",
+                    "raw_url": "https://github.com/octocat/Hello-World/raw/6dcb09b5b57875f334f61aebed695e2e4193db5e/file2.txt",
+                    "contents_url": "https://api.github.com/repos/octocat/Hello-World/contents/file2.txt?ref=6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                    "previous_filename": "old.txt"
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(CompareStatus::Diverged, comparison.status());
+        assert_eq!((1, 2), (comparison.ahead_by(), comparison.behind_by()));
+        assert_eq!((104, 22), (comparison.additions(), comparison.deletions()));
+        assert_eq!(FileStatus::Renamed, comparison.files()[1].status());
+        assert_eq!(&Some(String::from("old.txt")), comparison.files()[1].previous_filename());
+    }
+}
@@ -0,0 +1,269 @@
+//! For getting the files and directories of a repository.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::request::{self, Request};
+use crate::Result;
+
+/// Media type for getting a file as is, instead of as base64 in JSON.
+pub(crate) const RAW_MEDIA_TYPE: &str = "application/vnd.github.raw";
+
+/// A file, or the entries of a directory.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Contents {
+    Directory(Vec<Entry>),
+    File(Box<File>),
+}
+
+/// A file, directory, symlink or submodule, as it's listed in a directory.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Entry {
+    r#type: EntryType,
+    name: String,
+    path: String,
+    sha: String,
+    /// In bytes. `0` for directories.
+    size: u64,
+    url: String,
+    html_url: Option<String>,
+    git_url: Option<String>,
+    /// `None` for directories.
+    download_url: Option<String>,
+}
+
+/// A single file, symlink or submodule, with its content.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct File {
+    #[serde(flatten)]
+    entry: Entry,
+    /// `base64`, or `none` if the file is too large to include.
+    #[serde(default)]
+    encoding: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    /// Where a symlink points.
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    submodule_git_url: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryType {
+    File,
+    Dir,
+    Symlink,
+    Submodule,
+}
+
+/// A file's bytes, as returned by [`Repo::raw_file`](crate::Repo::raw_file).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawFile {
+    bytes: Vec<u8>,
+}
+
+impl Contents {
+    /// `None` if this is a directory.
+    pub fn file(&self) -> Option<&File> {
+        match self {
+            Contents::File(file) => Some(file.as_ref()),
+            Contents::Directory(_) => None,
+        }
+    }
+
+    /// `None` if this is a file.
+    pub fn entries(&self) -> Option<&[Entry]> {
+        match self {
+            Contents::Directory(entries) => Some(entries),
+            Contents::File(_) => None,
+        }
+    }
+}
+
+impl Entry {
+    pub fn r#type(&self) -> EntryType {
+        self.r#type
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// In bytes. `0` for directories.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &Option<String> {
+        &self.html_url
+    }
+
+    pub fn git_url(&self) -> &Option<String> {
+        &self.git_url
+    }
+
+    /// `None` for directories.
+    pub fn download_url(&self) -> &Option<String> {
+        &self.download_url
+    }
+}
+
+impl File {
+    /// Name, path, size and links of the file.
+    pub fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    /// `base64`, or `none` if the file is too large to include.
+    pub fn encoding(&self) -> &Option<String> {
+        &self.encoding
+    }
+
+    /// The content as [Github] sent it, usually base64 with line breaks.
+    ///
+    /// [Github]: https://github.com/
+    pub fn content(&self) -> &Option<String> {
+        &self.content
+    }
+
+    /// Where a symlink points.
+    pub fn target(&self) -> &Option<String> {
+        &self.target
+    }
+
+    pub fn submodule_git_url(&self) -> &Option<String> {
+        &self.submodule_git_url
+    }
+
+    /// Decodes the content.
+    ///
+    /// Fails for files larger than 1 MB, which [Github] doesn't include.
+    /// Use [`Repo::raw_file`](crate::Repo::raw_file) for those.
+    ///
+    /// [Github]: https://github.com/
+    pub fn decode(&self) -> Result<Vec<u8>> {
+        match (self.encoding.as_deref(), &self.content) {
+            (Some("base64"), Some(content)) => {
+                let content: String = content.split_whitespace().collect();
+                Ok(STANDARD.decode(content)?)
+            }
+            (Some("base64"), None) | (None, _) => {
+                Err(format!("`{}` has no content", self.entry.path).into())
+            }
+            (Some(encoding), _) => {
+                Err(format!("`{}` has unsupported encoding `{}`", self.entry.path, encoding).into())
+            }
+        }
+    }
+
+    /// Decodes the content as UTF-8 text.
+    pub fn text(&self) -> Result<String> {
+        Ok(String::from_utf8(self.decode()?)?)
+    }
+}
+
+impl RawFile {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The bytes as UTF-8 text.
+    pub fn text(&self) -> Result<&str> {
+        Ok(std::str::from_utf8(&self.bytes)?)
+    }
+}
+
+impl Request<RawFile> {
+    /// Sends the request.
+    pub async fn fetch(&self, user_agent: &str) -> Result<RawFile> {
+        let bytes = self.send(user_agent).await?.bytes().await?;
+        Ok(RawFile { bytes: bytes.to_vec() })
+    }
+
+    /// Sends the request, and returns `None` if the file wasn't found.
+    pub(crate) async fn fetch_optional(&self, user_agent: &str) -> Result<Option<RawFile>> {
+        match self.fetch(user_agent).await {
+            Ok(file) => Ok(Some(file)),
+            Err(e) if request::is_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    fn entry(r#type: &str, path: &str) -> Value {
+        json!({
+            "type": r#type,
+            "name": path.rsplit('/').next().unwrap(),
+            "path": path,
+            "sha": "3d21ec53a331a6f037a91c368710b99387d012c1",
+            "size": if r#type == "file" { 5362 } else { 0 },
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/contents/{}", path),
+            "html_url": format!("https://github.com/octocat/Hello-World/blob/master/{}", path),
+            "git_url": "https://api.github.com/repos/octocat/Hello-World/git/blobs/3d21ec53a331a6f037a91c368710b99387d012c1",
+            "download_url": if r#type == "file" {
+                Value::from(format!("https://raw.githubusercontent.com/octocat/Hello-World/master/{}", path))
+            } else {
+                Value::Null
+            }
+        })
+    }
+
+    #[test]
+    fn decoded_file() {
+        let mut file = entry("file", "README.md");
+        file["encoding"] = json!("base64");
+        file["content"] = json!("SGVsbG8g\nV29ybGQh\n");
+        let contents: Contents = serde_json::from_value(file).unwrap();
+        let file = contents.file().unwrap();
+
+        assert_eq!("README.md", file.entry().name());
+        assert_eq!("Hello World!", file.text().unwrap());
+    }
+
+    #[test]
+    fn directory_listing() {
+        let contents: Contents = serde_json::from_value(json!([
+            entry("file", "src/lib.rs"),
+            entry("dir", "src/metrics"),
+        ]))
+        .unwrap();
+        let types: Vec<_> = contents.entries().unwrap().iter().map(Entry::r#type).collect();
+
+        assert_eq!(vec![EntryType::File, EntryType::Dir], types);
+    }
+
+    #[test]
+    fn built_contents() {
+        assert_eq!(
+            "https://api.github.com/repos/octocat/Hello-World/contents/src/lib.rs?ref=v1.0",
+            repo(json!({})).contents("src/lib.rs", "v1.0").to_string(),
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! For getting deployments, their statuses, and environments.
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::request::List;
+use crate::User;
+
+/// A request to deploy a ref to an environment.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Deployment {
+    id: u64,
+    node_id: String,
+    url: String,
+    sha: String,
+    /// Branch, tag or SHA that was deployed.
+    r#ref: String,
+    /// Such as `deploy` or `deploy:migrations`.
+    task: String,
+    environment: String,
+    original_environment: Option<String>,
+    description: Option<String>,
+    /// `None` if the account was deleted.
+    creator: Option<User>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    statuses_url: String,
+    #[serde(default)]
+    production_environment: Option<bool>,
+    #[serde(default)]
+    transient_environment: Option<bool>,
+}
+
+/// The state of a [`Deployment`] at some point in time.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeploymentStatus {
+    id: u64,
+    node_id: String,
+    url: String,
+    state: DeploymentState,
+    /// `None` if the account was deleted.
+    creator: Option<User>,
+    description: Option<String>,
+    environment: Option<String>,
+    target_url: Option<String>,
+    #[serde(default)]
+    environment_url: Option<String>,
+    #[serde(default)]
+    log_url: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentState {
+    Error,
+    Failure,
+    Inactive,
+    InProgress,
+    Queued,
+    Pending,
+    Success,
+    /// A state that was added to [Github] after this was written.
+    ///
+    /// [Github]: https://github.com/
+    #[serde(other)]
+    Unknown,
+}
+
+impl DeploymentState {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, DeploymentState::Error | DeploymentState::Failure)
+    }
+}
+
+impl fmt::Display for DeploymentState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use DeploymentState::*;
+        write!(f, "{}", match self {
+            Error => "error",
+            Failure => "failure",
+            Inactive => "inactive",
+            InProgress => "in_progress",
+            Queued => "queued",
+            Pending => "pending",
+            Success => "success",
+            Unknown => "unknown",
+        })
+    }
+}
+
+/// A place that deployments go to, such as `production`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Environment {
+    id: u64,
+    node_id: String,
+    name: String,
+    url: String,
+    html_url: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Deployment {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// Branch, tag or SHA that was deployed.
+    pub fn r#ref(&self) -> &str {
+        &self.r#ref
+    }
+
+    /// Such as `deploy` or `deploy:migrations`.
+    pub fn task(&self) -> &str {
+        &self.task
+    }
+
+    pub fn environment(&self) -> &str {
+        &self.environment
+    }
+
+    pub fn original_environment(&self) -> &Option<String> {
+        &self.original_environment
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    /// `None` if the account was deleted.
+    pub fn creator(&self) -> &Option<User> {
+        &self.creator
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn statuses_url(&self) -> &str {
+        &self.statuses_url
+    }
+
+    pub fn production_environment(&self) -> &Option<bool> {
+        &self.production_environment
+    }
+
+    pub fn transient_environment(&self) -> &Option<bool> {
+        &self.transient_environment
+    }
+}
+
+impl DeploymentStatus {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn state(&self) -> DeploymentState {
+        self.state
+    }
+
+    /// `None` if the account was deleted.
+    pub fn creator(&self) -> &Option<User> {
+        &self.creator
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn environment(&self) -> &Option<String> {
+        &self.environment
+    }
+
+    pub fn target_url(&self) -> &Option<String> {
+        &self.target_url
+    }
+
+    pub fn environment_url(&self) -> &Option<String> {
+        &self.environment_url
+    }
+
+    pub fn log_url(&self) -> &Option<String> {
+        &self.log_url
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
+
+impl Environment {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
+
+/// Filters for [`Repo::deployments`](crate::Repo::deployments).
+impl List<Deployment> {
+    pub fn sha(self, sha: &str) -> Self {
+        self.param("sha", sha)
+    }
+
+    /// Only deployments of this branch, tag or SHA.
+    pub fn r#ref(self, r#ref: &str) -> Self {
+        self.param("ref", r#ref)
+    }
+
+    /// Such as `deploy`.
+    pub fn task(self, task: &str) -> Self {
+        self.param("task", task)
+    }
+
+    /// Such as `production`.
+    pub fn environment(self, environment: &str) -> Self {
+        self.param("environment", environment)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use crate::repository::tests::repo;
+    use crate::user::tests::user;
+
+    pub(crate) fn deployment(id: u64, sha: &str, environment: &str, created_at: &str) -> Value {
+        json!({
+            "id": id,
+            "node_id": "MDEwOkRlcGxveW1lbnQx",
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/deployments/{}", id),
+            "sha": sha,
+            "ref": "topic-branch",
+            "task": "deploy",
+            "environment": environment,
+            "original_environment": environment,
+            "description": "Deploy request from hubot",
+            "creator": user("octocat"),
+            "created_at": created_at,
+            "updated_at": created_at,
+            "statuses_url": format!("https://api.github.com/repos/octocat/Hello-World/deployments/{}/statuses", id),
+            "production_environment": environment == "production",
+            "transient_environment": false
+        })
+    }
+
+    pub(crate) fn status(state: &str, created_at: &str) -> Value {
+        json!({
+            "id": 1,
+            "node_id": "MDE2OkRlcGxveW1lbnRTdGF0dXMx",
+            "url": "https://api.github.com/repos/octocat/Hello-World/deployments/42/statuses/1",
+            "state": state,
+            "creator": user("octocat"),
+            "description": "Deployment finished successfully.",
+            "environment": "production",
+            "target_url": "https://example.com/deployment/42/output",
+            "environment_url": "https://test-branch.lab.acme.com",
+            "log_url": "https://example.com/deployment/42/output",
+            "created_at": created_at,
+            "updated_at": created_at
+        })
+    }
+
+    #[test]
+    fn built_deployments() {
+        assert_eq!(
+            "https://api.github.com/repos/octocat/Hello-World/deployments?environment=production&per_page=100",
+            repo(json!({})).deployments().environment("production").to_string(),
+        );
+    }
+}
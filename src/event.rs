@@ -0,0 +1,460 @@
+//! For getting the public activity of a repository.
+//!
+//! [Github] only keeps events for 90 days, and only lists the latest 300 of
+//! them.
+//!
+//! [Github]: https://github.com/
+
+use std::convert::TryFrom;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::ser::{SerializeMap, SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Most events [Github] lists, across every page.
+///
+/// [Github]: https://github.com/
+pub const MAX_EVENTS: usize = 300;
+
+/// Days [Github] keeps events for.
+///
+/// [Github]: https://github.com/
+pub const RETENTION_DAYS: i64 = 90;
+
+/// Something that happened in a repository.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "RawEvent")]
+pub struct Event {
+    id: String,
+    actor: Actor,
+    repo: EventRepo,
+    payload: Payload,
+    public: bool,
+    created_at: DateTime<Utc>,
+}
+
+/// An [`Event`] before its payload is parsed according to its type.
+#[derive(Deserialize)]
+struct RawEvent {
+    id: String,
+    r#type: String,
+    actor: Actor,
+    repo: EventRepo,
+    payload: Value,
+    public: bool,
+    created_at: DateTime<Utc>,
+}
+
+/// Who caused an [`Event`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Actor {
+    id: u64,
+    login: String,
+    #[serde(default)]
+    display_login: Option<String>,
+    url: String,
+    avatar_url: String,
+}
+
+/// The repository an [`Event`] happened in.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EventRepo {
+    id: u64,
+    /// Such as `octocat/Hello-World`.
+    name: String,
+    url: String,
+}
+
+/// What happened, by event type.
+///
+/// Event types that aren't covered here are [`Payload::Other`].
+#[derive(Debug)]
+pub enum Payload {
+    Push(PushPayload),
+    PullRequest(PullRequestPayload),
+    PullRequestReview(PullRequestPayload),
+    Issues(IssuesPayload),
+    IssueComment(IssuesPayload),
+    /// Someone starred the repository.
+    Watch(ActionPayload),
+    Fork(ForkPayload),
+    /// A branch or tag was created.
+    Create(RefPayload),
+    /// A branch or tag was deleted.
+    Delete(RefPayload),
+    Release(ReleasePayload),
+    /// Such as `GollumEvent`.
+    Other(String),
+}
+
+/// Commits pushed to a branch.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PushPayload {
+    push_id: u64,
+    /// Such as `refs/heads/main`.
+    r#ref: String,
+    head: String,
+    before: String,
+    /// Number of commits pushed. Missing from newer events.
+    #[serde(default)]
+    size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PullRequestPayload {
+    /// Such as `opened`, `closed` or `created`.
+    action: String,
+    #[serde(default)]
+    number: Option<u64>,
+    pull_request: Numbered,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssuesPayload {
+    /// Such as `opened`, `closed` or `created`.
+    action: String,
+    issue: Numbered,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ActionPayload {
+    /// `started` for a [`Payload::Watch`].
+    action: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForkPayload {
+    forkee: Forkee,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Forkee {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RefPayload {
+    /// `None` when a repository is created.
+    r#ref: Option<String>,
+    /// `branch`, `tag` or `repository`.
+    ref_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReleasePayload {
+    /// Such as `published`.
+    action: String,
+    release: Release,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Release {
+    tag_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Numbered {
+    number: u64,
+}
+
+impl TryFrom<RawEvent> for Event {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawEvent) -> serde_json::Result<Self> {
+        use serde_json::from_value;
+        let payload = match raw.r#type.as_str() {
+            "PushEvent" => Payload::Push(from_value(raw.payload)?),
+            "PullRequestEvent" => Payload::PullRequest(from_value(raw.payload)?),
+            "PullRequestReviewEvent" => Payload::PullRequestReview(from_value(raw.payload)?),
+            "IssuesEvent" => Payload::Issues(from_value(raw.payload)?),
+            "IssueCommentEvent" => Payload::IssueComment(from_value(raw.payload)?),
+            "WatchEvent" => Payload::Watch(from_value(raw.payload)?),
+            "ForkEvent" => Payload::Fork(from_value(raw.payload)?),
+            "CreateEvent" => Payload::Create(from_value(raw.payload)?),
+            "DeleteEvent" => Payload::Delete(from_value(raw.payload)?),
+            "ReleaseEvent" => Payload::Release(from_value(raw.payload)?),
+            _ => Payload::Other(raw.r#type),
+        };
+        Ok(Event {
+            id: raw.id,
+            actor: raw.actor,
+            repo: raw.repo,
+            payload,
+            public: raw.public,
+            created_at: raw.created_at,
+        })
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut event = serializer.serialize_struct("Event", 7)?;
+        event.serialize_field("id", &self.id)?;
+        event.serialize_field("type", self.payload.event_type())?;
+        event.serialize_field("actor", &self.actor)?;
+        event.serialize_field("repo", &self.repo)?;
+        event.serialize_field("payload", &self.payload)?;
+        event.serialize_field("public", &self.public)?;
+        event.serialize_field("created_at", &self.created_at)?;
+        event.end()
+    }
+}
+
+impl Event {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn actor(&self) -> &Actor {
+        &self.actor
+    }
+
+    pub fn repo(&self) -> &EventRepo {
+        &self.repo
+    }
+
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    pub fn public(&self) -> bool {
+        self.public
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
+
+impl Actor {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn login(&self) -> &str {
+        &self.login
+    }
+
+    pub fn display_login(&self) -> &Option<String> {
+        &self.display_login
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn avatar_url(&self) -> &str {
+        &self.avatar_url
+    }
+}
+
+impl EventRepo {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Such as `octocat/Hello-World`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Payload {
+    /// Such as `PushEvent`.
+    pub fn event_type(&self) -> &str {
+        use Payload::*;
+        match self {
+            Push(_) => "PushEvent",
+            PullRequest(_) => "PullRequestEvent",
+            PullRequestReview(_) => "PullRequestReviewEvent",
+            Issues(_) => "IssuesEvent",
+            IssueComment(_) => "IssueCommentEvent",
+            Watch(_) => "WatchEvent",
+            Fork(_) => "ForkEvent",
+            Create(_) => "CreateEvent",
+            Delete(_) => "DeleteEvent",
+            Release(_) => "ReleaseEvent",
+            Other(r#type) => r#type,
+        }
+    }
+}
+
+/// Serializes the payload alone, as [Github] sends it. [`Payload::Other`]
+/// becomes an empty object.
+///
+/// [Github]: https://github.com/
+impl Serialize for Payload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use Payload::*;
+        match self {
+            Push(payload) => payload.serialize(serializer),
+            PullRequest(payload) | PullRequestReview(payload) => payload.serialize(serializer),
+            Issues(payload) | IssueComment(payload) => payload.serialize(serializer),
+            Watch(payload) => payload.serialize(serializer),
+            Fork(payload) => payload.serialize(serializer),
+            Create(payload) | Delete(payload) => payload.serialize(serializer),
+            Release(payload) => payload.serialize(serializer),
+            Other(_) => serializer.serialize_map(Some(0))?.end(),
+        }
+    }
+}
+
+impl PushPayload {
+    pub fn push_id(&self) -> u64 {
+        self.push_id
+    }
+
+    /// Such as `refs/heads/main`.
+    pub fn r#ref(&self) -> &str {
+        &self.r#ref
+    }
+
+    pub fn head(&self) -> &str {
+        &self.head
+    }
+
+    pub fn before(&self) -> &str {
+        &self.before
+    }
+
+    /// Number of commits pushed. Missing from newer events.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+impl PullRequestPayload {
+    /// Such as `opened`, `closed` or `created`.
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number.unwrap_or(self.pull_request.number)
+    }
+}
+
+impl IssuesPayload {
+    /// Such as `opened`, `closed` or `created`.
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    pub fn number(&self) -> u64 {
+        self.issue.number
+    }
+}
+
+impl ActionPayload {
+    /// `started` for a [`Payload::Watch`].
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+}
+
+impl ForkPayload {
+    /// Such as `octocat/Hello-World`.
+    pub fn forkee(&self) -> &str {
+        &self.forkee.full_name
+    }
+}
+
+impl RefPayload {
+    /// `None` when a repository is created.
+    pub fn r#ref(&self) -> &Option<String> {
+        &self.r#ref
+    }
+
+    /// `branch`, `tag` or `repository`.
+    pub fn ref_type(&self) -> &str {
+        &self.ref_type
+    }
+}
+
+impl ReleasePayload {
+    /// Such as `published`.
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    pub fn tag_name(&self) -> &str {
+        &self.release.tag_name
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// An event of `type` by `login`, with `payload`.
+    pub(crate) fn event(r#type: &str, login: &str, created_at: &str, payload: Value) -> Value {
+        json!({
+            "id": "22249084947",
+            "type": r#type,
+            "actor": {
+                "id": 583231,
+                "login": login,
+                "display_login": login,
+                "gravatar_id": "",
+                "url": format!("https://api.github.com/users/{}", login),
+                "avatar_url": "https://avatars.githubusercontent.com/u/583231?"
+            },
+            "repo": {
+                "id": 1296269,
+                "name": "octocat/Hello-World",
+                "url": "https://api.github.com/repos/octocat/Hello-World"
+            },
+            "payload": payload,
+            "public": true,
+            "created_at": created_at
+        })
+    }
+
+    pub(crate) fn push(login: &str, created_at: &str) -> Value {
+        event("PushEvent", login, created_at, json!({
+            "repository_id": 1296269,
+            "push_id": 10115855396_u64,
+            "size": 1,
+            "distinct_size": 1,
+            "ref": "refs/heads/master",
+            "head": "7a8f3ac80e2ad2f6842cb86f576d4bfe2c03e300",
+            "before": "883efe034920928c47fe18598c01249d1a9fdabd",
+            "commits": []
+        }))
+    }
+
+    #[test]
+    fn typed_events() {
+        let events: Vec<Event> = serde_json::from_value(json!([
+            push("octocat", "2022-06-09T12:47:28Z"),
+            event("WatchEvent", "hubot", "2022-06-08T23:29:25Z", json!({"action": "started"})),
+            event("IssuesEvent", "hubot", "2022-06-07T07:50:26Z", json!({
+                "action": "opened",
+                "issue": {"number": 1347, "title": "Found a bug"}
+            })),
+            event("GollumEvent", "octocat", "2022-06-06T07:50:26Z", json!({"pages": []})),
+        ]))
+        .unwrap();
+
+        match events[0].payload() {
+            Payload::Push(push) => assert_eq!("refs/heads/master", push.r#ref()),
+            payload => panic!("expected a push, got {:?}", payload),
+        }
+        assert!(matches!(events[1].payload(), Payload::Watch(_)));
+        assert!(matches!(events[2].payload(), Payload::Issues(i) if i.number() == 1347));
+        assert!(matches!(events[3].payload(), Payload::Other(t) if t == "GollumEvent"));
+        assert_eq!("hubot", events[1].actor().login());
+
+        let json = serde_json::to_value(&events).unwrap();
+        let events: Vec<Event> = serde_json::from_value(json).unwrap();
+        assert!(matches!(events[1].payload(), Payload::Watch(_)));
+        assert_eq!("GollumEvent", events[3].payload().event_type());
+    }
+}
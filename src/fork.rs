@@ -0,0 +1,284 @@
+//! For exploring a repository's fork network.
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+
+use crate::{Repo, Result};
+
+/// Order of forks returned by [`Repo::forks`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForkSort {
+    Newest,
+    Oldest,
+    Stargazers,
+    Watchers,
+}
+
+impl Default for ForkSort {
+    /// [Github]'s default.
+    ///
+    /// [Github]: https://github.com/
+    fn default() -> Self {
+        ForkSort::Newest
+    }
+}
+
+impl fmt::Display for ForkSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ForkSort::*;
+        write!(f, "{}", match self {
+            Newest => "newest",
+            Oldest => "oldest",
+            Stargazers => "stargazers",
+            Watchers => "watchers",
+        })
+    }
+}
+
+/// A repository and its forks, including forks of forks.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::Repo;
+/// use github_stats::fork::ForkTree;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let tree = ForkTree::new(repo, 2, "<my user agent>", None).await?;
+///
+/// for fork in tree.most_active(10) {
+///     println!("{} ({} stars)", fork.full_name(), fork.stargazers_count());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ForkTree {
+    repo: Repo,
+    forks: Vec<ForkTree>,
+}
+
+impl ForkTree {
+    /// Builds the tree by listing forks until `max_depth` levels deep.
+    ///
+    /// A `max_depth` of `1` only lists direct forks. Repositories that report
+    /// no forks are not queried.
+    pub async fn new(
+        repo: Repo,
+        max_depth: usize,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let mut repos = vec![repo];
+        let mut parents = vec![None];
+        let mut depths = vec![0];
+
+        // Breadth first, so each index's parent has a lower index.
+        let mut i = 0;
+        while i < repos.len() {
+            if depths[i] < max_depth && repos[i].forks_count() > 0 {
+                let list = repos[i].forks(ForkSort::default()).authorized(authorization);
+                for fork in list.fetch(user_agent).await? {
+                    repos.push(fork);
+                    parents.push(Some(i));
+                    depths.push(depths[i] + 1);
+                }
+            }
+            i += 1;
+        }
+
+        Ok(ForkTree::assemble(repos, &parents))
+    }
+
+    // `parents[i]` is the index of the repository that `repos[i]` was forked
+    // from. The root is at index 0.
+    fn assemble(repos: Vec<Repo>, parents: &[Option<usize>]) -> Self {
+        let mut children = vec![Vec::new(); repos.len()];
+        for (i, parent) in parents.iter().enumerate() {
+            if let Some(parent) = parent {
+                children[*parent].push(i);
+            }
+        }
+        let mut repos: Vec<Option<Repo>> = repos.into_iter().map(Some).collect();
+        ForkTree::take(0, &mut repos, &children)
+    }
+
+    fn take(index: usize, repos: &mut [Option<Repo>], children: &[Vec<usize>]) -> Self {
+        let forks = children[index]
+            .iter()
+            .map(|&child| ForkTree::take(child, repos, children))
+            .collect();
+        let repo = repos[index].take().expect("each repository has one parent");
+        ForkTree { repo, forks }
+    }
+
+    pub fn repo(&self) -> &Repo {
+        &self.repo
+    }
+
+    /// Direct forks.
+    pub fn forks(&self) -> &[ForkTree] {
+        &self.forks
+    }
+
+    /// Number of forks at every depth, not including this repository.
+    pub fn fork_count(&self) -> usize {
+        self.forks.iter().map(|f| 1 + f.fork_count()).sum()
+    }
+
+    /// Summaries of every fork in the tree, in depth-first order.
+    pub fn summaries(&self) -> Vec<ForkSummary<'_>> {
+        let mut summaries = Vec::with_capacity(self.fork_count());
+        for fork in &self.forks {
+            fork.summarize(1, &mut summaries);
+        }
+        summaries
+    }
+
+    fn summarize<'a>(&'a self, depth: usize, summaries: &mut Vec<ForkSummary<'a>>) {
+        summaries.push(ForkSummary {
+            repo: &self.repo,
+            depth,
+            descendants: self.fork_count(),
+        });
+        for fork in &self.forks {
+            fork.summarize(depth + 1, summaries);
+        }
+    }
+
+    /// Up to `n` forks that were pushed to after being forked, most recently
+    /// pushed first.
+    pub fn most_active(&self, n: usize) -> Vec<ForkSummary<'_>> {
+        let mut active: Vec<_> = self
+            .summaries()
+            .into_iter()
+            .filter(ForkSummary::has_new_pushes)
+            .collect();
+        active.sort_by(|a, b| b.pushed_at().cmp(a.pushed_at()));
+        active.truncate(n);
+        active
+    }
+}
+
+/// Stats of a single fork in a [`ForkTree`].
+#[derive(Debug)]
+pub struct ForkSummary<'a> {
+    repo: &'a Repo,
+    depth: usize,
+    descendants: usize,
+}
+
+impl<'a> ForkSummary<'a> {
+    pub fn repo(&self) -> &'a Repo {
+        self.repo
+    }
+
+    pub fn full_name(&self) -> &'a str {
+        self.repo.full_name()
+    }
+
+    /// `1` for a direct fork, `2` for a fork of a fork, etc.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of forks of this fork, at every depth.
+    pub fn descendants(&self) -> usize {
+        self.descendants
+    }
+
+    pub fn stargazers_count(&self) -> u64 {
+        self.repo.stargazers_count()
+    }
+
+    pub fn forks_count(&self) -> u64 {
+        self.repo.forks_count()
+    }
+
+    pub fn open_issues_count(&self) -> u64 {
+        self.repo.open_issues_count()
+    }
+
+    pub fn pushed_at(&self) -> &'a DateTime<Utc> {
+        self.repo.pushed_at()
+    }
+
+    /// If the fork was pushed to after it was created.
+    ///
+    /// Forks that were never pushed to only contain the commits they were
+    /// forked with.
+    pub fn has_new_pushes(&self) -> bool {
+        self.repo.pushed_at() > self.repo.created_at()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    fn fork(name: &str, pushed_at: &str) -> Repo {
+        repo(json!({
+            "full_name": name,
+            "fork": true,
+            "created_at": "2020-01-01T00:00:00Z",
+            "pushed_at": pushed_at,
+        }))
+    }
+
+    fn tree() -> ForkTree {
+        let repos = vec![
+            repo(json!({})),
+            fork("a/Hello-World", "2020-01-01T00:00:00Z"),
+            fork("b/Hello-World", "2020-03-01T00:00:00Z"),
+            fork("c/Hello-World", "2020-02-01T00:00:00Z"),
+        ];
+        // c forked from a
+        ForkTree::assemble(repos, &[None, Some(0), Some(0), Some(1)])
+    }
+
+    #[test]
+    fn assembled_tree() {
+        let tree = tree();
+
+        assert_eq!(3, tree.fork_count());
+        assert_eq!(2, tree.forks().len());
+        assert_eq!("c/Hello-World", tree.forks()[0].forks()[0].repo().full_name());
+    }
+
+    #[test]
+    fn depth_first_summaries() {
+        let tree = tree();
+        let summaries: Vec<_> = tree
+            .summaries()
+            .iter()
+            .map(|s| (s.full_name(), s.depth(), s.descendants()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("a/Hello-World", 1, 1),
+                ("c/Hello-World", 2, 0),
+                ("b/Hello-World", 1, 0),
+            ],
+            summaries,
+        );
+    }
+
+    #[test]
+    fn most_active_forks() {
+        let tree = tree();
+        let active: Vec<_> = tree.most_active(10).iter().map(|s| s.full_name()).collect();
+
+        assert_eq!(vec!["b/Hello-World", "c/Hello-World"], active);
+    }
+
+    #[test]
+    fn fork_sort_string() {
+        assert_eq!("stargazers", ForkSort::Stargazers.to_string());
+    }
+}
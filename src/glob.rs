@@ -0,0 +1,80 @@
+// Shell-style wildcard matching.
+
+/// Checks if `text` matches `pattern`, where `*` matches any run of
+/// characters and `?` matches a single character.
+pub(crate) fn matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was tried at.
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                // Let the last `*` swallow one more character.
+                Some((star_p, star_t)) => {
+                    star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Checks if the `/` separated `path` matches `pattern`, where `*` and `?`
+/// don't match `/`, and a `**` segment matches any number of directories.
+pub(crate) fn matches_path(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    matches_segments(&pattern, &path)
+}
+
+fn matches_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| matches_segments(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path)) => matches(segment, name) && matches_segments(rest, path),
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("*.tar.gz", "app-x86_64-linux.tar.gz"));
+        assert!(matches("app-*-linux*", "app-x86_64-linux.tar.gz"));
+        assert!(matches("v?.?", "v1.0"));
+        assert!(matches("*", ""));
+        assert!(!matches("*.zip", "app.tar.gz"));
+        assert!(!matches("v?", "v10"));
+    }
+
+    #[test]
+    fn path_wildcards() {
+        assert!(matches_path("docs/*.md", "docs/README.md"));
+        assert!(!matches_path("docs/*.md", "docs/guides/setup.md"));
+        assert!(matches_path("**/logs", "build/logs"));
+        assert!(matches_path("**/logs", "logs"));
+        assert!(matches_path("src/**/test?.rs", "src/a/b/test1.rs"));
+        assert!(matches_path("src/**", "src/lib.rs"));
+    }
+}
@@ -0,0 +1,416 @@
+//! For getting issues, their comments and their events.
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::label::Label;
+use crate::milestone::Milestone;
+use crate::request::{Direction, List};
+use crate::User;
+
+/// An issue as [Github] reports it.
+///
+/// [Github] considers every pull request an issue, so lists of issues can
+/// include pull requests. See [`Issue::is_pull_request`].
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Issue {
+    id: u64,
+    node_id: String,
+    number: u64,
+    url: String,
+    html_url: String,
+    title: String,
+    body: Option<String>,
+    user: User,
+    labels: Vec<Label>,
+    state: State,
+    locked: bool,
+    assignees: Vec<User>,
+    milestone: Option<Milestone>,
+    /// Number of comments.
+    comments: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    author_association: String,
+    /// Only included for pull requests.
+    #[serde(default)]
+    pull_request: Option<IssuePullRequest>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct IssuePullRequest {
+    url: String,
+}
+
+/// A comment on an [`Issue`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssueComment {
+    id: u64,
+    node_id: String,
+    url: String,
+    html_url: String,
+    body: String,
+    /// `None` if the account was deleted.
+    user: Option<User>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    author_association: String,
+}
+
+/// Something that happened to an [`Issue`], such as being closed or labeled.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssueEvent {
+    id: u64,
+    node_id: String,
+    url: String,
+    /// `None` if the account was deleted.
+    actor: Option<User>,
+    /// Such as `closed`, `reopened`, `labeled` or `assigned`.
+    event: String,
+    commit_id: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Open,
+    Closed,
+}
+
+/// Which states to list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateFilter {
+    Open,
+    Closed,
+    All,
+}
+
+impl fmt::Display for StateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use StateFilter::*;
+        write!(f, "{}", match self {
+            Open => "open",
+            Closed => "closed",
+            All => "all",
+        })
+    }
+}
+
+/// Order of issues returned by [`Repo::issues`](crate::Repo::issues).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssueSort {
+    Created,
+    Updated,
+    Comments,
+}
+
+impl fmt::Display for IssueSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use IssueSort::*;
+        write!(f, "{}", match self {
+            Created => "created",
+            Updated => "updated",
+            Comments => "comments",
+        })
+    }
+}
+
+impl Issue {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn body(&self) -> &Option<String> {
+        &self.body
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub fn has_label(&self, name: &str) -> bool {
+        self.labels.iter().any(|l| l.name() == name)
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn assignees(&self) -> &[User] {
+        &self.assignees
+    }
+
+    pub fn milestone(&self) -> &Option<Milestone> {
+        &self.milestone
+    }
+
+    /// Number of comments.
+    pub fn comments(&self) -> u64 {
+        self.comments
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn closed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.closed_at
+    }
+
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+
+    /// If this "issue" is actually a pull request.
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    /// API link to the pull request, if this is one.
+    pub fn pull_request_url(&self) -> Option<&str> {
+        self.pull_request.as_ref().map(|p| p.url.as_str())
+    }
+}
+
+impl IssueComment {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// `None` if the account was deleted.
+    pub fn user(&self) -> &Option<User> {
+        &self.user
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+
+    /// If the commenter owns the repository, or is a member or collaborator.
+    pub fn is_maintainer(&self) -> bool {
+        matches!(self.author_association.as_str(), "OWNER" | "MEMBER" | "COLLABORATOR")
+    }
+}
+
+impl IssueEvent {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// `None` if the account was deleted.
+    pub fn actor(&self) -> &Option<User> {
+        &self.actor
+    }
+
+    /// Such as `closed`, `reopened`, `labeled` or `assigned`.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    pub fn commit_id(&self) -> &Option<String> {
+        &self.commit_id
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
+
+/// Filters for [`Repo::issues`](crate::Repo::issues).
+impl List<Issue> {
+    /// Defaults to [`StateFilter::Open`].
+    pub fn state(self, state: StateFilter) -> Self {
+        self.param("state", state)
+    }
+
+    /// Only issues with every one of these labels.
+    pub fn labels(self, labels: &[&str]) -> Self {
+        self.param("labels", labels.join(","))
+    }
+
+    /// Only issues created by this user.
+    pub fn creator(self, username: &str) -> Self {
+        self.param("creator", username)
+    }
+
+    /// Only issues assigned to this user.
+    ///
+    /// `none` lists unassigned issues, and `*` lists assigned issues.
+    pub fn assignee(self, username: &str) -> Self {
+        self.param("assignee", username)
+    }
+
+    /// Only issues updated at or after this time.
+    pub fn since(self, since: &DateTime<Utc>) -> Self {
+        self.param("since", since.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+
+    /// Defaults to [`IssueSort::Created`].
+    pub fn sort(self, sort: IssueSort) -> Self {
+        self.param("sort", sort)
+    }
+
+    /// Defaults to [`Direction::Desc`].
+    pub fn direction(self, direction: Direction) -> Self {
+        self.param("direction", direction)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::label::tests::label;
+    use crate::repository::tests::repo;
+    use crate::user::tests::user;
+
+    /// An issue opened by `octocat`, as [Github] lists it.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn issue(number: u64, labels: &[&str], created_at: &str, closed_at: Option<&str>) -> Value {
+        json!({
+            "id": number,
+            "node_id": "MDU6SXNzdWUx",
+            "number": number,
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/issues/{}", number),
+            "html_url": format!("https://github.com/octocat/Hello-World/issues/{}", number),
+            "title": "Found a bug",
+            "body": "I'm having a problem with this.",
+            "user": user("octocat"),
+            "labels": labels.iter().map(|l| label(l)).collect::<Vec<_>>(),
+            "state": if closed_at.is_some() { "closed" } else { "open" },
+            "locked": false,
+            "assignees": [],
+            "comments": 0,
+            "created_at": created_at,
+            "updated_at": closed_at.unwrap_or(created_at),
+            "closed_at": closed_at,
+            "author_association": "NONE"
+        })
+    }
+
+    pub(crate) fn comment(login: &str, author_association: &str, created_at: &str) -> Value {
+        json!({
+            "id": 1,
+            "node_id": "MDEyOklzc3VlQ29tbWVudDE=",
+            "url": "https://api.github.com/repos/octocat/Hello-World/issues/comments/1",
+            "html_url": "https://github.com/octocat/Hello-World/issues/1347#issuecomment-1",
+            "body": "Me too",
+            "user": user(login),
+            "created_at": created_at,
+            "updated_at": created_at,
+            "author_association": author_association
+        })
+    }
+
+    pub(crate) fn event(event: &str, created_at: &str) -> Value {
+        json!({
+            "id": 1,
+            "node_id": "MDEwOklzc3VlRXZlbnQx",
+            "url": "https://api.github.com/repos/octocat/Hello-World/issues/events/1",
+            "actor": user("octocat"),
+            "event": event,
+            "commit_id": null,
+            "created_at": created_at
+        })
+    }
+
+    #[test]
+    fn built_issues() {
+        const EXPECTED: &str = "https://api.github.com/repos/octocat/Hello-World/issues?state=all&labels=bug%2Cui&per_page=100";
+        let issues = repo(json!({}))
+            .issues()
+            .state(StateFilter::All)
+            .labels(&["bug", "ui"]);
+
+        assert_eq!(EXPECTED, issues.to_string());
+    }
+
+    #[test]
+    fn pull_request_issue() {
+        let mut pull = issue(1347, &["bug"], "2011-04-22T13:33:48Z", None);
+        pull["pull_request"] = json!({
+            "url": "https://api.github.com/repos/octocat/Hello-World/pulls/1347",
+            "html_url": "https://github.com/octocat/Hello-World/pull/1347",
+            "diff_url": "https://github.com/octocat/Hello-World/pull/1347.diff",
+            "patch_url": "https://github.com/octocat/Hello-World/pull/1347.patch"
+        });
+        let pull: Issue = serde_json::from_value(pull).unwrap();
+        let issue: Issue = serde_json::from_value(issue(1, &[], "2011-04-22T13:33:48Z", None)).unwrap();
+
+        assert!(pull.is_pull_request());
+        assert!(pull.has_label("bug"));
+        assert!(!issue.is_pull_request());
+    }
+}
@@ -0,0 +1,165 @@
+//! For getting the labels of issues and pull requests.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::issue::{Issue, State};
+use crate::{Query, Repo, Result, Search};
+
+/// A label that can be added to issues and pull requests.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Label {
+    id: u64,
+    node_id: String,
+    url: String,
+    name: String,
+    description: Option<String>,
+    /// Hexadecimal color code, without the leading `#`.
+    color: String,
+    default: bool,
+}
+
+impl Label {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    /// Hexadecimal color code, without the leading `#`.
+    pub fn color(&self) -> &str {
+        &self.color
+    }
+
+    /// If the label is one of [Github]'s default labels.
+    ///
+    /// [Github]: https://github.com/
+    pub fn default(&self) -> bool {
+        self.default
+    }
+}
+
+/// Counts open issues per label from issues that have already been listed.
+///
+/// Pull requests and closed issues are skipped, so every label's count can
+/// come from a single [`Repo::issues`] listing.
+pub fn open_issue_counts(issues: &[Issue]) -> BTreeMap<String, u64> {
+    let mut counts = BTreeMap::new();
+    let open = issues
+        .iter()
+        .filter(|i| i.state() == State::Open && !i.is_pull_request());
+    for issue in open {
+        for label in issue.labels() {
+            *counts.entry(label.name().to_owned()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// A search for open issues in `repo` that have the label `name`.
+///
+/// Results in `repo:owner/name+is:issue+label:name+state:open`.
+pub fn open_issues_query(repo: &Repo, name: &str) -> Query {
+    let name = if name.contains(char::is_whitespace) {
+        format!("\"{}\"", name)
+    } else {
+        String::from(name)
+    };
+    Query::new()
+        .repo(repo.owner().login(), repo.name())
+        .is("issue")
+        .label(&name)
+        .state("open")
+}
+
+/// Counts open issues with the label `name` with a single search.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::{label, Repo};
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let bugs = label::count_open_issues(&repo, "C-bug", "<my user agent>", None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn count_open_issues(
+    repo: &Repo,
+    name: &str,
+    user_agent: &str,
+    authorization: Option<&str>,
+) -> Result<u64> {
+    let search = Search::issues(&open_issues_query(repo, name)).per_page(1);
+    let search = match authorization {
+        Some(t) => search.authorization(t),
+        None => search,
+    };
+    Ok(search.search(user_agent).await?.total_count())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::issue::tests::issue;
+    use crate::repository::tests::repo;
+
+    pub(crate) fn label(name: &str) -> Value {
+        json!({
+            "id": 208045946,
+            "node_id": "MDU6TGFiZWwyMDgwNDU5NDY=",
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/labels/{}", name),
+            "name": name,
+            "description": null,
+            "color": "f29513",
+            "default": false
+        })
+    }
+
+    #[test]
+    fn counted_labels() {
+        let mut pull = issue(4, &["bug"], "2020-01-01T00:00:00Z", None);
+        pull["pull_request"] = json!({"url": "https://api.github.com/repos/octocat/Hello-World/pulls/4"});
+        let issues: Vec<Issue> = vec![
+            issue(1, &["bug", "ui"], "2020-01-01T00:00:00Z", None),
+            issue(2, &["bug"], "2020-01-01T00:00:00Z", None),
+            issue(3, &["bug"], "2020-01-01T00:00:00Z", Some("2020-01-02T00:00:00Z")),
+            pull,
+        ]
+        .into_iter()
+        .map(|i| serde_json::from_value(i).unwrap())
+        .collect();
+        let counts = open_issue_counts(&issues);
+
+        assert_eq!(Some(&2), counts.get("bug"));
+        assert_eq!(Some(&1), counts.get("ui"));
+    }
+
+    #[test]
+    fn label_query() {
+        let repo = repo(json!({}));
+
+        assert_eq!(
+            "repo:octocat/Hello-World+is:issue+label:\"good first issue\"+state:open",
+            open_issues_query(&repo, "good first issue").to_string(),
+        );
+    }
+}
@@ -40,12 +40,37 @@
 //!
 //! [Github]: https://github.com/
 
-pub use repository::Repo;
+pub use repository::{Repo, RepoList, RepoSort, RepoType};
 pub use search::{Query, Search};
-pub use user::User;
+pub use user::{AuthenticatedUser, Plan, User, UserProfile};
 
+pub mod actions;
+pub mod alert;
+pub mod check;
+pub mod codeowners;
+pub mod collaborator;
+pub mod commit;
+pub mod community;
+pub mod content;
+pub mod compare;
+pub mod deployment;
+pub mod event;
+pub mod fork;
+mod glob;
+pub mod issue;
+pub mod label;
+pub mod metrics;
+pub mod milestone;
+pub mod org;
+pub mod pull;
+pub mod release;
 mod repository;
+pub mod request;
+pub mod sbom;
 pub mod search;
+pub mod snapshot;
+pub mod stargazer;
+pub mod tree;
 mod user;
 
 /// This crate's standard error type.
@@ -0,0 +1,165 @@
+//! Statistics computed from data that has already been fetched.
+
+use std::iter::FromIterator;
+
+use chrono::prelude::{DateTime, Utc};
+use chrono::Duration;
+
+pub mod actions;
+pub mod activity;
+pub mod dora;
+pub mod flakiness;
+pub mod issues;
+pub mod pulls;
+pub mod repos;
+
+/// A span of time that metrics are computed over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Window {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+impl Window {
+    /// Includes `start`, but not `end`.
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Window { start, end }
+    }
+
+    /// The `duration` up to now.
+    pub fn last(duration: Duration) -> Self {
+        let end = Utc::now();
+        Window::new(end - duration, end)
+    }
+
+    pub fn start(&self) -> &DateTime<Utc> {
+        &self.start
+    }
+
+    pub fn end(&self) -> &DateTime<Utc> {
+        &self.end
+    }
+
+    pub fn contains(&self, date: &DateTime<Utc>) -> bool {
+        &self.start <= date && date < &self.end
+    }
+
+    /// Length of the window in days, including partial days.
+    pub fn days(&self) -> f64 {
+        (self.end - self.start).num_seconds() as f64 / 86_400.0
+    }
+}
+
+/// A set of durations, such as how long each pull request took to merge.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Distribution {
+    /// Sorted shortest first.
+    durations: Vec<Duration>,
+}
+
+impl Distribution {
+    pub fn new(mut durations: Vec<Duration>) -> Self {
+        durations.sort();
+        Distribution { durations }
+    }
+
+    /// Shortest first.
+    pub fn durations(&self) -> &[Duration] {
+        &self.durations
+    }
+
+    pub fn len(&self) -> usize {
+        self.durations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.durations.is_empty()
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.durations.first().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.durations.last().copied()
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        if self.durations.is_empty() {
+            return None;
+        }
+        let total: i64 = self.durations.iter().map(Duration::num_milliseconds).sum();
+        Some(Duration::milliseconds(total / self.durations.len() as i64))
+    }
+
+    /// Interpolates between the two nearest durations when `percentile`
+    /// falls between them.
+    ///
+    /// `percentile` is clamped to `0.0..=100.0`.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let last = self.durations.len().checked_sub(1)?;
+        let rank = percentile.clamp(0.0, 100.0) / 100.0 * last as f64;
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        let lower_ms = self.durations[lower].num_milliseconds();
+        let upper_ms = self.durations[upper].num_milliseconds();
+        let fraction = rank - lower as f64;
+        let ms = lower_ms as f64 + (upper_ms - lower_ms) as f64 * fraction;
+        Some(Duration::milliseconds(ms.round() as i64))
+    }
+
+    pub fn median(&self) -> Option<Duration> {
+        self.percentile(50.0)
+    }
+
+    pub fn p90(&self) -> Option<Duration> {
+        self.percentile(90.0)
+    }
+
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(95.0)
+    }
+}
+
+impl FromIterator<Duration> for Distribution {
+    fn from_iter<I: IntoIterator<Item = Duration>>(iter: I) -> Self {
+        Distribution::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles() {
+        let distribution: Distribution = vec![4, 1, 3, 2]
+            .into_iter()
+            .map(Duration::hours)
+            .collect();
+
+        assert_eq!(Some(Duration::hours(1)), distribution.min());
+        assert_eq!(Some(Duration::minutes(150)), distribution.median());
+        assert_eq!(Some(Duration::minutes(150)), distribution.mean());
+        assert_eq!(Some(Duration::minutes(222)), distribution.p90());
+        assert_eq!(Some(Duration::hours(4)), distribution.percentile(100.0));
+    }
+
+    #[test]
+    fn empty_distribution() {
+        let distribution = Distribution::default();
+
+        assert_eq!(None, distribution.median());
+        assert_eq!(None, distribution.mean());
+    }
+
+    #[test]
+    fn window_bounds() {
+        let start = "2020-01-01T00:00:00Z".parse().unwrap();
+        let end = "2020-01-08T00:00:00Z".parse().unwrap();
+        let window = Window::new(start, end);
+
+        assert!(window.contains(&start));
+        assert!(!window.contains(&end));
+        assert_eq!(7.0, window.days());
+    }
+}
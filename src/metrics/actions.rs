@@ -0,0 +1,237 @@
+//! [Github Actions] workflow run metrics.
+//!
+//! [Github Actions]: https://docs.github.com/en/actions
+
+use std::collections::BTreeMap;
+
+use chrono::prelude::NaiveDate;
+
+use crate::actions::{Conclusion, Status, WorkflowRun};
+use crate::{Repo, Result};
+
+use super::{Distribution, Window};
+
+/// How often and how quickly a workflow ran in a [`Window`].
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Duration;
+/// use github_stats::Repo;
+/// use github_stats::metrics::Window;
+/// use github_stats::metrics::actions::WorkflowStats;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let window = Window::last(Duration::days(7));
+///
+/// for stats in WorkflowStats::fetch(&repo, &window, "<my user agent>", None).await? {
+///     println!("{}: {:?}", stats.name(), stats.success_rate());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WorkflowStats {
+    workflow_id: u64,
+    name: String,
+    runs: usize,
+    succeeded: usize,
+    failed: usize,
+    duration: Distribution,
+    queue_time: Distribution,
+    daily_runs: BTreeMap<NaiveDate, usize>,
+    days: f64,
+}
+
+impl WorkflowStats {
+    /// Computes stats for each workflow with runs created in `window`.
+    ///
+    /// Sorted by workflow ID.
+    pub fn per_workflow(runs: &[WorkflowRun], window: &Window) -> Vec<Self> {
+        let mut workflows: BTreeMap<u64, Vec<&WorkflowRun>> = BTreeMap::new();
+        for run in runs.iter().filter(|r| window.contains(r.created_at())) {
+            workflows.entry(run.workflow_id()).or_default().push(run);
+        }
+        workflows
+            .into_iter()
+            .map(|(workflow_id, runs)| WorkflowStats::new(workflow_id, &runs, window))
+            .collect()
+    }
+
+    /// Lists the runs created in `window` and computes stats for each
+    /// workflow.
+    ///
+    /// [Github] only lists the latest 1000 runs of a window, so the stats of a
+    /// busy window leave out its earliest runs.
+    ///
+    /// [Github]: https://github.com/
+    pub async fn fetch(
+        repo: &Repo,
+        window: &Window,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Vec<Self>> {
+        let runs = repo
+            .workflow_runs()
+            .created_between(window.start(), window.end())
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        Ok(WorkflowStats::per_workflow(&runs, window))
+    }
+
+    fn new(workflow_id: u64, runs: &[&WorkflowRun], window: &Window) -> Self {
+        let name = runs
+            .iter()
+            .find_map(|r| r.name().clone())
+            .unwrap_or_else(|| workflow_id.to_string());
+        let conclusions = runs.iter().filter_map(|r| r.conclusion());
+        let succeeded = conclusions
+            .clone()
+            .filter(|c| *c == Conclusion::Success)
+            .count();
+        let failed = conclusions.filter(Conclusion::is_failure).count();
+        let duration = runs
+            .iter()
+            .filter(|r| r.status() == Some(Status::Completed))
+            .filter_map(|r| r.run_started_at().map(|s| *r.updated_at() - s))
+            .collect();
+        // Reruns keep the original creation time, so only first attempts
+        // show how long the run was queued.
+        let queue_time = runs
+            .iter()
+            .filter(|r| r.run_attempt() == 1)
+            .filter_map(|r| r.run_started_at().map(|s| s - *r.created_at()))
+            .collect();
+        let mut daily_runs = BTreeMap::new();
+        for run in runs {
+            *daily_runs.entry(run.created_at().date_naive()).or_insert(0) += 1;
+        }
+
+        WorkflowStats {
+            workflow_id,
+            name,
+            runs: runs.len(),
+            succeeded,
+            failed,
+            duration,
+            queue_time,
+            daily_runs,
+            days: window.days(),
+        }
+    }
+
+    pub fn workflow_id(&self) -> u64 {
+        self.workflow_id
+    }
+
+    /// Name of the workflow, or its ID if no run had a name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs created in the window, including ones that haven't completed.
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+
+    /// Runs that failed, timed out or failed to start.
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Fraction of runs that succeeded, out of those that succeeded or
+    /// failed.
+    ///
+    /// Cancelled, skipped and unfinished runs are ignored. `None` if no runs
+    /// succeeded or failed.
+    pub fn success_rate(&self) -> Option<f64> {
+        let total = self.succeeded + self.failed;
+        if total == 0 {
+            None
+        } else {
+            Some(self.succeeded as f64 / total as f64)
+        }
+    }
+
+    /// From when the latest attempt started to when it completed, for
+    /// completed runs.
+    pub fn duration(&self) -> &Distribution {
+        &self.duration
+    }
+
+    /// From creation to when a runner started the first attempt.
+    pub fn queue_time(&self) -> &Distribution {
+        &self.queue_time
+    }
+
+    /// Average number of runs created per day of the window.
+    pub fn runs_per_day(&self) -> f64 {
+        if self.days > 0.0 {
+            self.runs as f64 / self.days
+        } else {
+            0.0
+        }
+    }
+
+    /// Number of runs created on each UTC day. Days without runs are left
+    /// out.
+    pub fn daily_runs(&self) -> &BTreeMap<NaiveDate, usize> {
+        &self.daily_runs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::actions::tests::run;
+
+    const SHA: &str = "acb5820ced9479c074f688cc328bf03f341a511d";
+
+    #[test]
+    fn workflow_stats() {
+        let mut other = run(5, SHA, 1, Some("success"), "2020-01-02T00:00:00Z", "2020-01-02T00:01:00Z", "2020-01-02T00:02:00Z");
+        other["workflow_id"] = 2.into();
+        other["name"] = "Docs".into();
+        let runs: Vec<WorkflowRun> = vec![
+            run(1, SHA, 1, Some("success"), "2020-01-01T00:00:00Z", "2020-01-01T00:01:00Z", "2020-01-01T00:11:00Z"),
+            run(2, SHA, 2, Some("failure"), "2020-01-01T12:00:00Z", "2020-01-02T00:00:00Z", "2020-01-02T00:20:00Z"),
+            run(3, SHA, 1, Some("cancelled"), "2020-01-02T00:00:00Z", "2020-01-02T00:03:00Z", "2020-01-02T00:05:00Z"),
+            run(4, SHA, 1, None, "2020-01-02T06:00:00Z", "2020-01-02T06:05:00Z", "2020-01-02T06:06:00Z"),
+            // Created before the window
+            run(6, SHA, 1, Some("failure"), "2019-12-31T00:00:00Z", "2019-12-31T00:01:00Z", "2019-12-31T00:02:00Z"),
+            other,
+        ]
+        .into_iter()
+        .map(|r| serde_json::from_value(r).unwrap())
+        .collect();
+        let window = Window::new(
+            "2020-01-01T00:00:00Z".parse().unwrap(),
+            "2020-01-03T00:00:00Z".parse().unwrap(),
+        );
+        let stats = WorkflowStats::per_workflow(&runs, &window);
+        let ci = &stats[1];
+
+        assert_eq!(vec![2, 159038], stats.iter().map(WorkflowStats::workflow_id).collect::<Vec<_>>());
+        assert_eq!("Docs", stats[0].name());
+        assert_eq!(4, ci.runs());
+        assert_eq!(Some(0.5), ci.success_rate());
+        assert_eq!(
+            vec![Duration::minutes(2), Duration::minutes(10), Duration::minutes(20)],
+            ci.duration().durations(),
+        );
+        assert_eq!(
+            vec![Duration::minutes(1), Duration::minutes(3), Duration::minutes(5)],
+            ci.queue_time().durations(),
+        );
+        assert_eq!(2.0, ci.runs_per_day());
+        assert_eq!(vec![2, 2], ci.daily_runs().values().copied().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,165 @@
+//! Repository activity from its event timeline.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::prelude::{DateTime, NaiveDate, Utc};
+use chrono::Duration;
+
+use crate::event::{Event, Payload, MAX_EVENTS, RETENTION_DAYS};
+use crate::{Repo, Result};
+
+use super::Window;
+
+/// Pushes and actors of the events in a [`Window`].
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Duration;
+/// use github_stats::Repo;
+/// use github_stats::metrics::Window;
+/// use github_stats::metrics::activity::ActivitySummary;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let window = Window::last(Duration::days(7));
+/// let summary = ActivitySummary::fetch(&repo, &window, "<my user agent>", None).await?;
+///
+/// println!("{} actors, {} pushes", summary.actors().len(), summary.pushes());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ActivitySummary {
+    events: usize,
+    pushes: usize,
+    daily_pushes: BTreeMap<NaiveDate, usize>,
+    actors: BTreeSet<String>,
+    complete: bool,
+    days: f64,
+}
+
+impl ActivitySummary {
+    /// Summarizes the events created in `window`.
+    ///
+    /// `events` should be listed at `now`, newest first, as [`Repo::events`]
+    /// lists them.
+    pub fn new(events: &[Event], window: &Window, now: &DateTime<Utc>) -> Self {
+        let mut daily_pushes = BTreeMap::new();
+        let mut actors = BTreeSet::new();
+        let mut count = 0;
+        for event in events.iter().filter(|e| window.contains(e.created_at())) {
+            count += 1;
+            actors.insert(String::from(event.actor().login()));
+            if let Payload::Push(_) = event.payload() {
+                *daily_pushes.entry(event.created_at().date_naive()).or_insert(0) += 1;
+            }
+        }
+        // The listing reaches past the start of the window, or it's every
+        // event Github still has and the window is within its retention.
+        let retained_since = *now - Duration::days(RETENTION_DAYS);
+        let complete = events.iter().any(|e| e.created_at() < window.start())
+            || (events.len() < MAX_EVENTS && window.start() >= &retained_since);
+        ActivitySummary {
+            events: count,
+            pushes: daily_pushes.values().sum(),
+            daily_pushes,
+            actors,
+            complete,
+            days: window.days(),
+        }
+    }
+
+    /// Lists the latest events of `repo` and summarizes those in `window`.
+    pub async fn fetch(
+        repo: &Repo,
+        window: &Window,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let events = repo
+            .events()
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        Ok(ActivitySummary::new(&events, window, &Utc::now()))
+    }
+
+    pub fn events(&self) -> usize {
+        self.events
+    }
+
+    pub fn pushes(&self) -> usize {
+        self.pushes
+    }
+
+    /// Pushes per day of the window.
+    pub fn pushes_per_day(&self) -> f64 {
+        if self.days > 0.0 {
+            self.pushes as f64 / self.days
+        } else {
+            0.0
+        }
+    }
+
+    /// Pushes on each day that had any.
+    pub fn daily_pushes(&self) -> &BTreeMap<NaiveDate, usize> {
+        &self.daily_pushes
+    }
+
+    /// Logins of everyone who caused an event.
+    pub fn actors(&self) -> &BTreeSet<String> {
+        &self.actors
+    }
+
+    /// If every event in the window was listed.
+    ///
+    /// `false` when the window starts more than 90 days ago, or when it had
+    /// more than the latest 300 events.
+    pub fn complete(&self) -> bool {
+        self.complete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::event::tests::{event, push};
+
+    #[test]
+    fn activity_summary() {
+        let events: Vec<Event> = serde_json::from_value(json!([
+            push("octocat", "2022-06-09T12:47:28Z"),
+            push("octocat", "2022-06-09T10:00:00Z"),
+            event("WatchEvent", "hubot", "2022-06-08T23:29:25Z", json!({"action": "started"})),
+            push("hubot", "2022-06-07T07:50:26Z"),
+            push("monalisa", "2022-05-01T00:00:00Z"),
+        ]))
+        .unwrap();
+        let window = Window::new(
+            "2022-06-06T00:00:00Z".parse().unwrap(),
+            "2022-06-10T00:00:00Z".parse().unwrap(),
+        );
+        let summary = ActivitySummary::new(&events, &window, window.end());
+        let june_9 = NaiveDate::from_ymd_opt(2022, 6, 9).unwrap();
+
+        assert_eq!(4, summary.events());
+        assert_eq!(3, summary.pushes());
+        assert_eq!(0.75, summary.pushes_per_day());
+        assert_eq!(Some(&2), summary.daily_pushes().get(&june_9));
+        assert_eq!(vec!["hubot", "octocat"], summary.actors().iter().collect::<Vec<_>>());
+        assert!(summary.complete());
+        // Starts more than 90 days before the listing
+        let long = Window::new("2022-01-01T00:00:00Z".parse().unwrap(), *window.end());
+        assert!(!ActivitySummary::new(&events[..4], &long, window.end()).complete());
+        // Ended in the past, but started before the oldest retained events
+        let now = "2022-08-08T00:00:00Z".parse().unwrap();
+        let past = Window::new(
+            "2022-04-30T00:00:00Z".parse().unwrap(),
+            "2022-06-10T00:00:00Z".parse().unwrap(),
+        );
+        assert!(!ActivitySummary::new(&events[..4], &past, &now).complete());
+    }
+}
@@ -0,0 +1,283 @@
+//! [DORA] metrics from deployments: deployment frequency, lead time for
+//! changes, change failure rate and time to restore service.
+//!
+//! [DORA]: https://dora.dev/
+
+use std::collections::BTreeMap;
+
+use chrono::prelude::{DateTime, Utc};
+
+use crate::deployment::{Deployment, DeploymentState, DeploymentStatus};
+use crate::{Repo, Result};
+
+use super::{Distribution, Window};
+
+/// A deployment with its statuses, and when its commit was made.
+#[derive(Debug)]
+pub struct DeploymentHistory {
+    deployment: Deployment,
+    statuses: Vec<DeploymentStatus>,
+    committed_at: Option<DateTime<Utc>>,
+}
+
+impl DeploymentHistory {
+    pub fn new(
+        deployment: Deployment,
+        statuses: Vec<DeploymentStatus>,
+        committed_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        DeploymentHistory { deployment, statuses, committed_at }
+    }
+
+    /// Lists the deployments to `environment` created in `window`, with
+    /// their statuses and commits.
+    ///
+    /// Every deployment to `environment` is listed, since they can't be
+    /// filtered by date. This makes two more requests per deployment in
+    /// `window`.
+    pub async fn fetch_all(
+        repo: &Repo,
+        environment: &str,
+        window: &Window,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Vec<Self>> {
+        let deployments = repo
+            .deployments()
+            .environment(environment)
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+
+        let mut histories = Vec::new();
+        for deployment in deployments.into_iter().filter(|d| window.contains(d.created_at())) {
+            let statuses = repo
+                .deployment_statuses(deployment.id())
+                .authorized(authorization)
+                .fetch(user_agent)
+                .await?;
+            let commit = repo
+                .commit(deployment.sha())
+                .authorized(authorization)
+                .fetch(user_agent)
+                .await?;
+            let committed_at = commit.commit().committer().as_ref().map(|c| *c.date());
+            histories.push(DeploymentHistory::new(deployment, statuses, committed_at));
+        }
+        Ok(histories)
+    }
+
+    pub fn deployment(&self) -> &Deployment {
+        &self.deployment
+    }
+
+    pub fn statuses(&self) -> &[DeploymentStatus] {
+        &self.statuses
+    }
+
+    /// When the deployed commit was committed.
+    pub fn committed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.committed_at
+    }
+
+    /// When the deployment first succeeded.
+    pub fn succeeded_at(&self) -> Option<&DateTime<Utc>> {
+        self.first(|s| s == DeploymentState::Success)
+    }
+
+    /// When the deployment first failed or errored, if it never succeeded.
+    pub fn failed_at(&self) -> Option<&DateTime<Utc>> {
+        if self.succeeded_at().is_some() {
+            None
+        } else {
+            self.first(|s| s.is_failure())
+        }
+    }
+
+    fn first<F: Fn(DeploymentState) -> bool>(&self, state: F) -> Option<&DateTime<Utc>> {
+        self.statuses
+            .iter()
+            .filter(|s| state(s.state()))
+            .map(DeploymentStatus::created_at)
+            .min()
+    }
+}
+
+/// The four [DORA] metrics for deployments created in a [`Window`].
+///
+/// A deployment counts as successful if any of its statuses is `success`,
+/// and as failed if it never succeeded but has a `failure` or `error`
+/// status. Deployments that are still pending count as neither.
+///
+/// [DORA]: https://dora.dev/
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Duration;
+/// use github_stats::Repo;
+/// use github_stats::metrics::Window;
+/// use github_stats::metrics::dora::{DeploymentHistory, DoraMetrics};
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let window = Window::last(Duration::days(30));
+/// let histories = DeploymentHistory::fetch_all(&repo, "production", &window, "<my user agent>", None).await?;
+/// let metrics = DoraMetrics::new(&histories, &window);
+///
+/// println!("Deployments per day: {}", metrics.deployment_frequency());
+/// println!("Median lead time: {:?}", metrics.lead_time().median());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DoraMetrics {
+    deployments: usize,
+    succeeded: usize,
+    failed: usize,
+    lead_time: Distribution,
+    time_to_restore: Distribution,
+    days: f64,
+}
+
+impl DoraMetrics {
+    pub fn new(histories: &[DeploymentHistory], window: &Window) -> Self {
+        let mut environments: BTreeMap<&str, Vec<&DeploymentHistory>> = BTreeMap::new();
+        for history in histories.iter().filter(|h| window.contains(h.deployment.created_at())) {
+            environments
+                .entry(history.deployment.environment())
+                .or_default()
+                .push(history);
+        }
+
+        let mut deployments = 0;
+        let mut succeeded = 0;
+        let mut failed = 0;
+        let mut lead_time = Vec::new();
+        let mut time_to_restore = Vec::new();
+        for mut histories in environments.into_values() {
+            histories.sort_by_key(|h| h.deployment.created_at());
+            deployments += histories.len();
+            // When the environment first failed since it last succeeded.
+            let mut outage: Option<&DateTime<Utc>> = None;
+            for history in histories {
+                if let Some(succeeded_at) = history.succeeded_at() {
+                    succeeded += 1;
+                    if let Some(committed_at) = history.committed_at {
+                        lead_time.push(*succeeded_at - committed_at);
+                    }
+                    if let Some(start) = outage.take() {
+                        time_to_restore.push(*succeeded_at - *start);
+                    }
+                } else if let Some(failed_at) = history.failed_at() {
+                    failed += 1;
+                    outage = outage.or(Some(failed_at));
+                }
+            }
+        }
+
+        DoraMetrics {
+            deployments,
+            succeeded,
+            failed,
+            lead_time: Distribution::new(lead_time),
+            time_to_restore: Distribution::new(time_to_restore),
+            days: window.days(),
+        }
+    }
+
+    /// Deployments created in the window.
+    pub fn deployments(&self) -> usize {
+        self.deployments
+    }
+
+    pub fn succeeded(&self) -> usize {
+        self.succeeded
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Successful deployments per day.
+    pub fn deployment_frequency(&self) -> f64 {
+        if self.days > 0.0 {
+            self.succeeded as f64 / self.days
+        } else {
+            0.0
+        }
+    }
+
+    /// From when the deployed commit was committed to when the deployment
+    /// first succeeded.
+    pub fn lead_time(&self) -> &Distribution {
+        &self.lead_time
+    }
+
+    /// Fraction of finished deployments that failed.
+    ///
+    /// `None` if no deployments finished.
+    pub fn change_failure_rate(&self) -> Option<f64> {
+        let total = self.succeeded + self.failed;
+        if total == 0 {
+            None
+        } else {
+            Some(self.failed as f64 / total as f64)
+        }
+    }
+
+    /// From a failed deployment to the next successful deployment to the same
+    /// environment.
+    ///
+    /// Consecutive failures count as a single outage, from the first failure.
+    pub fn time_to_restore(&self) -> &Distribution {
+        &self.time_to_restore
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::deployment::tests::{deployment, status};
+
+    const SHA: &str = "a84d88e7554fc1fa21bcbc4efae3c782a70d2b9d";
+
+    fn history(id: u64, environment: &str, created_at: &str, statuses: &[(&str, &str)]) -> DeploymentHistory {
+        DeploymentHistory::new(
+            serde_json::from_value(deployment(id, SHA, environment, created_at)).unwrap(),
+            statuses
+                .iter()
+                .map(|(state, at)| serde_json::from_value(status(state, at)).unwrap())
+                .collect(),
+            Some("2020-01-01T00:00:00Z".parse().unwrap()),
+        )
+    }
+
+    #[test]
+    fn dora_metrics() {
+        let histories = vec![
+            history(1, "production", "2020-01-01T10:00:00Z", &[("success", "2020-01-01T12:00:00Z"), ("inactive", "2020-01-02T00:00:00Z")]),
+            history(2, "production", "2020-01-02T00:00:00Z", &[("failure", "2020-01-02T01:00:00Z")]),
+            history(3, "production", "2020-01-02T02:00:00Z", &[("error", "2020-01-02T03:00:00Z")]),
+            history(4, "production", "2020-01-02T04:00:00Z", &[("in_progress", "2020-01-02T04:30:00Z"), ("success", "2020-01-02T05:00:00Z")]),
+            history(5, "production", "2020-01-03T00:00:00Z", &[("pending", "2020-01-03T00:00:00Z")]),
+            history(6, "staging", "2020-01-02T00:00:00Z", &[("success", "2020-01-02T00:00:00Z")]),
+        ];
+        let window = Window::new(
+            "2020-01-01T00:00:00Z".parse().unwrap(),
+            "2020-01-04T00:00:00Z".parse().unwrap(),
+        );
+        let metrics = DoraMetrics::new(&histories, &window);
+
+        assert_eq!(6, metrics.deployments());
+        assert_eq!(1.0, metrics.deployment_frequency());
+        assert_eq!(Some(0.4), metrics.change_failure_rate());
+        assert_eq!(
+            vec![Duration::hours(12), Duration::hours(24), Duration::hours(29)],
+            metrics.lead_time().durations(),
+        );
+        assert_eq!(vec![Duration::hours(4)], metrics.time_to_restore().durations());
+    }
+}
@@ -0,0 +1,373 @@
+//! CI flakiness, from jobs that failed and then passed on the same commit.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::actions::{Conclusion, Job, JobFilter, WorkflowRun};
+use crate::{Repo, Result};
+
+use super::Window;
+
+// A job with the run it belongs to.
+type Attempt<'a> = (&'a WorkflowRun, &'a Job);
+
+/// A job that failed and then passed on a rerun of the same commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Flake {
+    workflow_id: u64,
+    workflow_name: String,
+    job_name: String,
+    head_sha: String,
+    failed_run_id: u64,
+    failed_attempt: u64,
+    passed_run_id: u64,
+    passed_attempt: u64,
+}
+
+impl Flake {
+    pub fn workflow_id(&self) -> u64 {
+        self.workflow_id
+    }
+
+    pub fn workflow_name(&self) -> &str {
+        &self.workflow_name
+    }
+
+    pub fn job_name(&self) -> &str {
+        &self.job_name
+    }
+
+    pub fn head_sha(&self) -> &str {
+        &self.head_sha
+    }
+
+    pub fn failed_run_id(&self) -> u64 {
+        self.failed_run_id
+    }
+
+    pub fn failed_attempt(&self) -> u64 {
+        self.failed_attempt
+    }
+
+    pub fn passed_run_id(&self) -> u64 {
+        self.passed_run_id
+    }
+
+    pub fn passed_attempt(&self) -> u64 {
+        self.passed_attempt
+    }
+}
+
+/// How often a workflow or job flaked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlakeRate {
+    workflow_id: u64,
+    workflow_name: String,
+    /// `None` for a whole workflow.
+    job_name: Option<String>,
+    commits: usize,
+    flaky_commits: usize,
+}
+
+impl FlakeRate {
+    pub fn workflow_id(&self) -> u64 {
+        self.workflow_id
+    }
+
+    pub fn workflow_name(&self) -> &str {
+        &self.workflow_name
+    }
+
+    /// `None` for a whole workflow.
+    pub fn job_name(&self) -> &Option<String> {
+        &self.job_name
+    }
+
+    /// Commits that the workflow ran on in the window.
+    pub fn commits(&self) -> usize {
+        self.commits
+    }
+
+    /// Commits with at least one flake.
+    pub fn flaky_commits(&self) -> usize {
+        self.flaky_commits
+    }
+
+    /// Fraction of commits with at least one flake.
+    ///
+    /// `None` if the workflow didn't run.
+    pub fn rate(&self) -> Option<f64> {
+        if self.commits == 0 {
+            None
+        } else {
+            Some(self.flaky_commits as f64 / self.commits as f64)
+        }
+    }
+}
+
+/// Flaky jobs found in workflow runs created in a [`Window`].
+///
+/// Every attempt of every run on a commit is considered, so a job counts as
+/// flaky whether it was rerun with "Re-run jobs" or the whole workflow was
+/// triggered again.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Duration;
+/// use github_stats::Repo;
+/// use github_stats::metrics::Window;
+/// use github_stats::metrics::flakiness::FlakeReport;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let window = Window::last(Duration::days(30));
+/// let report = FlakeReport::fetch(&repo, &window, "<my user agent>", None).await?;
+///
+/// for job in report.jobs().iter().take(10) {
+///     println!("{:?}: {:?}", job.job_name(), job.rate());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FlakeReport {
+    flakes: Vec<Flake>,
+    workflows: Vec<FlakeRate>,
+    jobs: Vec<FlakeRate>,
+}
+
+impl FlakeReport {
+    /// Finds flakes among the runs created in `window`.
+    ///
+    /// `jobs` maps run IDs to the jobs of every attempt of that run. Runs
+    /// without an entry are treated as having no jobs. A job's flake rate is
+    /// out of every commit its workflow ran on.
+    pub fn new(runs: &[WorkflowRun], jobs: &HashMap<u64, Vec<Job>>, window: &Window) -> Self {
+        let runs: Vec<_> = runs
+            .iter()
+            .filter(|r| window.contains(r.created_at()))
+            .collect();
+
+        let mut names = BTreeMap::new();
+        let mut commits: BTreeMap<u64, BTreeSet<&str>> = BTreeMap::new();
+        // Every attempt of a job, keyed by workflow, commit and job name.
+        let mut attempts: BTreeMap<(u64, &str, &str), Vec<Attempt>> = BTreeMap::new();
+        for run in &runs {
+            if let Some(name) = run.name() {
+                names.entry(run.workflow_id()).or_insert_with(|| name.clone());
+            }
+            commits.entry(run.workflow_id()).or_default().insert(run.head_sha());
+            for job in jobs.get(&run.id()).into_iter().flatten() {
+                attempts
+                    .entry((run.workflow_id(), run.head_sha(), job.name()))
+                    .or_default()
+                    .push((run, job));
+            }
+        }
+        let name = |workflow_id: u64| {
+            names
+                .get(&workflow_id)
+                .cloned()
+                .unwrap_or_else(|| workflow_id.to_string())
+        };
+
+        let mut flakes = Vec::new();
+        for ((workflow_id, head_sha, job_name), mut attempts) in attempts {
+            attempts.sort_by_key(|(run, job)| (*job.started_at(), run.id(), job.run_attempt()));
+            let failed = attempts
+                .iter()
+                .position(|(_, job)| job.conclusion().is_some_and(|c| c.is_failure()));
+            let passed = failed.and_then(|failed| {
+                attempts[failed..]
+                    .iter()
+                    .find(|(_, job)| job.conclusion() == Some(Conclusion::Success))
+                    .map(|passed| (attempts[failed], *passed))
+            });
+            if let Some(((failed_run, failed_job), (passed_run, passed_job))) = passed {
+                flakes.push(Flake {
+                    workflow_id,
+                    workflow_name: name(workflow_id),
+                    job_name: job_name.to_owned(),
+                    head_sha: head_sha.to_owned(),
+                    failed_run_id: failed_run.id(),
+                    failed_attempt: failed_job.run_attempt(),
+                    passed_run_id: passed_run.id(),
+                    passed_attempt: passed_job.run_attempt(),
+                });
+            }
+        }
+
+        let mut workflows: Vec<_> = commits
+            .iter()
+            .map(|(&workflow_id, commits)| {
+                let flaky_commits: BTreeSet<_> = flakes
+                    .iter()
+                    .filter(|f| f.workflow_id == workflow_id)
+                    .map(|f| &f.head_sha)
+                    .collect();
+                FlakeRate {
+                    workflow_id,
+                    workflow_name: name(workflow_id),
+                    job_name: None,
+                    commits: commits.len(),
+                    flaky_commits: flaky_commits.len(),
+                }
+            })
+            .collect();
+        let mut job_flakes: BTreeMap<(u64, &str), usize> = BTreeMap::new();
+        for flake in &flakes {
+            *job_flakes.entry((flake.workflow_id, &flake.job_name)).or_insert(0) += 1;
+        }
+        let mut jobs: Vec<_> = job_flakes
+            .into_iter()
+            .map(|((workflow_id, job_name), flaky_commits)| FlakeRate {
+                workflow_id,
+                workflow_name: name(workflow_id),
+                job_name: Some(job_name.to_owned()),
+                commits: commits[&workflow_id].len(),
+                flaky_commits,
+            })
+            .collect();
+        workflows.sort_by(by_rate);
+        jobs.sort_by(by_rate);
+
+        FlakeReport { flakes, workflows, jobs }
+    }
+
+    /// Lists the runs created in `window`, and the jobs of every attempt of
+    /// the runs that could have flaked.
+    ///
+    /// Jobs are only listed for runs that were rerun, or that share their
+    /// commit with another run of the same workflow, which makes one request
+    /// per such run.
+    pub async fn fetch(
+        repo: &Repo,
+        window: &Window,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let runs = repo
+            .workflow_runs()
+            .created_between(window.start(), window.end())
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+
+        let mut per_commit: HashMap<(u64, &str), usize> = HashMap::new();
+        for run in &runs {
+            *per_commit.entry((run.workflow_id(), run.head_sha())).or_insert(0) += 1;
+        }
+        let mut jobs = HashMap::new();
+        for run in &runs {
+            if run.run_attempt() > 1 || per_commit[&(run.workflow_id(), run.head_sha())] > 1 {
+                let run_jobs = repo
+                    .run_jobs(run.id())
+                    .filter(JobFilter::All)
+                    .authorized(authorization)
+                    .fetch(user_agent)
+                    .await?;
+                jobs.insert(run.id(), run_jobs);
+            }
+        }
+        Ok(FlakeReport::new(&runs, &jobs, window))
+    }
+
+    /// Sorted by workflow, commit and job name.
+    pub fn flakes(&self) -> &[Flake] {
+        &self.flakes
+    }
+
+    /// Workflows that ran, most flaky first.
+    pub fn workflows(&self) -> &[FlakeRate] {
+        &self.workflows
+    }
+
+    /// Jobs that flaked at least once, most flaky first.
+    pub fn jobs(&self) -> &[FlakeRate] {
+        &self.jobs
+    }
+}
+
+// Highest rate first, then the most flaky commits, then by name.
+fn by_rate(a: &FlakeRate, b: &FlakeRate) -> Ordering {
+    let rate = |r: &FlakeRate| r.rate().unwrap_or(0.0);
+    rate(b)
+        .partial_cmp(&rate(a))
+        .unwrap_or(Ordering::Equal)
+        .then(b.flaky_commits.cmp(&a.flaky_commits))
+        .then_with(|| a.workflow_name.cmp(&b.workflow_name))
+        .then_with(|| a.job_name.cmp(&b.job_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::actions::tests::{job, run};
+
+    const FIRST: &str = "acb5820ced9479c074f688cc328bf03f341a511d";
+    const SECOND: &str = "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d";
+    const THIRD: &str = "762941318ee16e59dabbacb1b4049eec22f0d303";
+
+    fn parse<T: serde::de::DeserializeOwned>(values: Vec<Value>) -> Vec<T> {
+        values.into_iter().map(|v| serde_json::from_value(v).unwrap()).collect()
+    }
+
+    #[test]
+    fn flake_report() {
+        let runs = parse(vec![
+            // Rerun after `test` failed
+            run(1, FIRST, 2, Some("success"), "2020-01-01T00:00:00Z", "2020-01-01T01:00:00Z", "2020-01-01T01:10:00Z"),
+            // Triggered again after `lint` timed out
+            run(2, SECOND, 1, Some("timed_out"), "2020-01-02T00:00:00Z", "2020-01-02T00:01:00Z", "2020-01-02T00:30:00Z"),
+            run(3, SECOND, 1, Some("success"), "2020-01-02T01:00:00Z", "2020-01-02T01:01:00Z", "2020-01-02T01:10:00Z"),
+            // Failed every time
+            run(4, THIRD, 2, Some("failure"), "2020-01-03T00:00:00Z", "2020-01-03T01:00:00Z", "2020-01-03T01:10:00Z"),
+        ]);
+        let jobs: HashMap<u64, Vec<Job>> = vec![
+            (1, parse(vec![
+                job(10, 1, 1, FIRST, "test", Some("failure"), "2020-01-01T00:01:00Z"),
+                job(11, 1, 1, FIRST, "lint", Some("success"), "2020-01-01T00:01:00Z"),
+                job(12, 1, 2, FIRST, "test", Some("success"), "2020-01-01T01:00:00Z"),
+                job(13, 1, 2, FIRST, "lint", Some("success"), "2020-01-01T01:00:00Z"),
+            ])),
+            (2, parse(vec![
+                job(20, 2, 1, SECOND, "test", Some("success"), "2020-01-02T00:01:00Z"),
+                job(21, 2, 1, SECOND, "lint", Some("timed_out"), "2020-01-02T00:01:00Z"),
+            ])),
+            (3, parse(vec![
+                job(30, 3, 1, SECOND, "test", Some("success"), "2020-01-02T01:01:00Z"),
+                job(31, 3, 1, SECOND, "lint", Some("success"), "2020-01-02T01:01:00Z"),
+            ])),
+            (4, parse(vec![
+                job(40, 4, 1, THIRD, "test", Some("failure"), "2020-01-03T00:01:00Z"),
+                job(41, 4, 2, THIRD, "test", Some("failure"), "2020-01-03T01:00:00Z"),
+            ])),
+        ]
+        .into_iter()
+        .collect();
+        let window = Window::new(
+            "2020-01-01T00:00:00Z".parse().unwrap(),
+            "2020-01-08T00:00:00Z".parse().unwrap(),
+        );
+        let report = FlakeReport::new(&runs, &jobs, &window);
+        let flakes: Vec<_> = report
+            .flakes()
+            .iter()
+            .map(|f| (f.job_name(), f.head_sha(), f.failed_run_id(), f.passed_run_id(), f.passed_attempt()))
+            .collect();
+
+        assert_eq!(2, flakes.len());
+        assert!(flakes.contains(&("test", FIRST, 1, 1, 2)));
+        assert!(flakes.contains(&("lint", SECOND, 2, 3, 1)));
+        assert_eq!(1, report.workflows().len());
+        assert_eq!(3, report.workflows()[0].commits());
+        assert_eq!(Some(2.0 / 3.0), report.workflows()[0].rate());
+        assert_eq!(
+            vec![Some("lint"), Some("test")],
+            report.jobs().iter().map(|j| j.job_name().as_deref()).collect::<Vec<_>>(),
+        );
+    }
+}
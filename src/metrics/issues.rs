@@ -0,0 +1,327 @@
+//! Issue lifecycle metrics.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::prelude::{DateTime, Utc};
+
+use crate::issue::{Issue, IssueComment, IssueEvent, State, StateFilter};
+use crate::{Repo, Result};
+
+use super::{Distribution, Window};
+
+/// An issue with its comments and events.
+#[derive(Debug)]
+pub struct IssueHistory {
+    issue: Issue,
+    comments: Vec<IssueComment>,
+    events: Vec<IssueEvent>,
+}
+
+impl IssueHistory {
+    pub fn new(issue: Issue, comments: Vec<IssueComment>, events: Vec<IssueEvent>) -> Self {
+        IssueHistory { issue, comments, events }
+    }
+
+    /// Lists the repository's issues, and the comments and events of those
+    /// created in `window`.
+    ///
+    /// Every open issue is listed so that [`IssueMetrics::open_age`] can be
+    /// computed, but only issues updated since the start of `window` are
+    /// listed otherwise. Pull requests are skipped. This makes two requests
+    /// per issue created in `window`.
+    pub async fn fetch_all(
+        repo: &Repo,
+        window: &Window,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Vec<Self>> {
+        let mut issues = repo
+            .issues()
+            .state(StateFilter::Open)
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        let open: BTreeSet<u64> = issues.iter().map(Issue::number).collect();
+        let updated = repo
+            .issues()
+            .state(StateFilter::All)
+            .since(window.start())
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        issues.extend(updated.into_iter().filter(|i| !open.contains(&i.number())));
+
+        let mut histories = Vec::new();
+        for issue in issues.into_iter().filter(|i| !i.is_pull_request()) {
+            let (comments, events) = if window.contains(issue.created_at()) {
+                let comments = repo
+                    .issue_comments(issue.number())
+                    .authorized(authorization)
+                    .fetch(user_agent)
+                    .await?;
+                let events = repo
+                    .issue_events(issue.number())
+                    .authorized(authorization)
+                    .fetch(user_agent)
+                    .await?;
+                (comments, events)
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            histories.push(IssueHistory::new(issue, comments, events));
+        }
+        Ok(histories)
+    }
+
+    pub fn issue(&self) -> &Issue {
+        &self.issue
+    }
+
+    pub fn comments(&self) -> &[IssueComment] {
+        &self.comments
+    }
+
+    pub fn events(&self) -> &[IssueEvent] {
+        &self.events
+    }
+
+    /// When a maintainer other than the author first commented.
+    pub fn first_response(&self) -> Option<&DateTime<Utc>> {
+        let author = self.issue.user().login();
+        self.comments
+            .iter()
+            .filter(|c| c.is_maintainer())
+            .filter(|c| c.user().as_ref().map_or(true, |u| u.login() != author))
+            .map(IssueComment::created_at)
+            .min()
+    }
+
+    /// If the issue was ever reopened.
+    pub fn reopened(&self) -> bool {
+        self.events.iter().any(|e| e.event() == "reopened")
+    }
+}
+
+/// How quickly issues opened in a [`Window`] were responded to and closed.
+///
+/// Pull requests are always excluded.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Duration;
+/// use github_stats::Repo;
+/// use github_stats::metrics::Window;
+/// use github_stats::metrics::issues::{IssueHistory, IssueMetrics};
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let window = Window::last(Duration::days(7));
+/// let histories = IssueHistory::fetch_all(&repo, &window, "<my user agent>", None).await?;
+///
+/// for (label, metrics) in IssueMetrics::by_label(&histories, &window) {
+///     println!("{}: {:?}", label, metrics.time_to_first_response().median());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IssueMetrics {
+    opened: usize,
+    closed: usize,
+    reopened: usize,
+    time_to_first_response: Distribution,
+    time_to_close: Distribution,
+    open_age: Distribution,
+}
+
+impl IssueMetrics {
+    /// Computes metrics for issues created in `window`.
+    ///
+    /// [`open_age`](IssueMetrics::open_age) instead covers every issue that
+    /// was created before the end of `window` and is still open.
+    pub fn new(histories: &[IssueHistory], window: &Window) -> Self {
+        IssueMetrics::from_histories(histories.iter(), window)
+    }
+
+    /// Computes metrics separately for each label.
+    ///
+    /// An issue with several labels counts toward each of them. Unlabeled
+    /// issues are skipped.
+    pub fn by_label(histories: &[IssueHistory], window: &Window) -> BTreeMap<String, Self> {
+        let labels: BTreeSet<&str> = histories
+            .iter()
+            .flat_map(|h| h.issue.labels())
+            .map(|l| l.name())
+            .collect();
+        labels
+            .into_iter()
+            .map(|label| {
+                let histories = histories.iter().filter(|h| h.issue.has_label(label));
+                (label.to_owned(), IssueMetrics::from_histories(histories, window))
+            })
+            .collect()
+    }
+
+    fn from_histories<'a, I>(histories: I, window: &Window) -> Self
+    where
+        I: Iterator<Item = &'a IssueHistory> + Clone,
+    {
+        let issues = histories.filter(|h| !h.issue.is_pull_request());
+        let open_age = issues
+            .clone()
+            .filter(|h| h.issue.state() == State::Open)
+            .filter(|h| h.issue.created_at() < window.end())
+            .map(|h| *window.end() - *h.issue.created_at())
+            .collect();
+
+        let opened: Vec<_> = issues
+            .filter(|h| window.contains(h.issue.created_at()))
+            .collect();
+        let closed = opened
+            .iter()
+            .filter(|h| h.issue.closed_at().is_some())
+            .count();
+        let reopened = opened.iter().filter(|h| h.reopened()).count();
+        let time_to_first_response = opened
+            .iter()
+            .filter_map(|h| h.first_response().map(|r| *r - *h.issue.created_at()))
+            .collect();
+        let time_to_close = opened
+            .iter()
+            .filter_map(|h| h.issue.closed_at().map(|c| c - *h.issue.created_at()))
+            .collect();
+
+        IssueMetrics {
+            opened: opened.len(),
+            closed,
+            reopened,
+            time_to_first_response,
+            time_to_close,
+            open_age,
+        }
+    }
+
+    /// Issues created in the window.
+    pub fn opened(&self) -> usize {
+        self.opened
+    }
+
+    /// Issues created in the window that are now closed.
+    pub fn closed(&self) -> usize {
+        self.closed
+    }
+
+    /// Issues created in the window that were reopened at least once.
+    pub fn reopened(&self) -> usize {
+        self.reopened
+    }
+
+    /// Fraction of issues created in the window that were reopened.
+    ///
+    /// `None` if no issues were created.
+    pub fn reopen_rate(&self) -> Option<f64> {
+        if self.opened == 0 {
+            None
+        } else {
+            Some(self.reopened as f64 / self.opened as f64)
+        }
+    }
+
+    /// From creation to the first comment by a maintainer other than the
+    /// author, for issues that have one.
+    pub fn time_to_first_response(&self) -> &Distribution {
+        &self.time_to_first_response
+    }
+
+    /// From creation to the last time it was closed, for closed issues.
+    pub fn time_to_close(&self) -> &Distribution {
+        &self.time_to_close
+    }
+
+    /// Age of open issues at the end of the window.
+    pub fn open_age(&self) -> &Distribution {
+        &self.open_age
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::issue::tests::{comment, event, issue};
+
+    fn parse<T: serde::de::DeserializeOwned>(values: Vec<Value>) -> Vec<T> {
+        values.into_iter().map(|v| serde_json::from_value(v).unwrap()).collect()
+    }
+
+    fn history(issue: Value, comments: Vec<Value>, events: Vec<Value>) -> IssueHistory {
+        IssueHistory::new(serde_json::from_value(issue).unwrap(), parse(comments), parse(events))
+    }
+
+    fn histories() -> Vec<IssueHistory> {
+        let mut pull = issue(5, &["bug"], "2020-01-01T00:00:00Z", None);
+        pull["pull_request"] = serde_json::json!({"url": "https://api.github.com/repos/octocat/Hello-World/pulls/5"});
+        vec![
+            history(
+                issue(1, &["bug"], "2020-01-01T00:00:00Z", Some("2020-01-03T00:00:00Z")),
+                vec![
+                    comment("hubot", "NONE", "2020-01-01T01:00:00Z"),
+                    comment("monalisa", "MEMBER", "2020-01-01T04:00:00Z"),
+                ],
+                vec![
+                    event("closed", "2020-01-02T00:00:00Z"),
+                    event("reopened", "2020-01-02T12:00:00Z"),
+                    event("closed", "2020-01-03T00:00:00Z"),
+                ],
+            ),
+            history(
+                issue(2, &["bug", "ui"], "2020-01-02T00:00:00Z", None),
+                vec![comment("monalisa", "OWNER", "2020-01-02T02:00:00Z")],
+                vec![],
+            ),
+            history(issue(3, &[], "2020-01-03T00:00:00Z", None), vec![], vec![]),
+            // Opened before the window
+            history(issue(4, &["ui"], "2019-12-01T00:00:00Z", None), vec![], vec![]),
+            history(pull, vec![], vec![]),
+        ]
+    }
+
+    fn window() -> Window {
+        Window::new(
+            "2020-01-01T00:00:00Z".parse().unwrap(),
+            "2020-01-11T00:00:00Z".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn issue_metrics() {
+        let metrics = IssueMetrics::new(&histories(), &window());
+
+        assert_eq!(3, metrics.opened());
+        assert_eq!(1, metrics.closed());
+        assert_eq!(Some(1.0 / 3.0), metrics.reopen_rate());
+        assert_eq!(
+            vec![Duration::hours(2), Duration::hours(4)],
+            metrics.time_to_first_response().durations(),
+        );
+        assert_eq!(Some(Duration::days(2)), metrics.time_to_close().median());
+        assert_eq!(
+            vec![Duration::days(8), Duration::days(9), Duration::days(41)],
+            metrics.open_age().durations(),
+        );
+    }
+
+    #[test]
+    fn label_buckets() {
+        let metrics = IssueMetrics::by_label(&histories(), &window());
+        let labels: Vec<_> = metrics.keys().map(String::as_str).collect();
+
+        assert_eq!(vec!["bug", "ui"], labels);
+        assert_eq!(2, metrics["bug"].opened());
+        assert_eq!(1, metrics["ui"].opened());
+        assert_eq!(2, metrics["ui"].open_age().len());
+    }
+}
@@ -0,0 +1,227 @@
+//! Pull request lifecycle metrics.
+
+use std::collections::HashMap;
+
+use chrono::prelude::{DateTime, Utc};
+
+use crate::pull::{PullRequest, PullSort, Review, ReviewState, StateFilter};
+use crate::request::Direction;
+use crate::{Repo, Result};
+
+use super::{Distribution, Window};
+
+/// How quickly pull requests opened in a [`Window`] were reviewed and merged.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use chrono::Duration;
+/// use github_stats::Repo;
+/// use github_stats::metrics::Window;
+/// use github_stats::metrics::pulls::PullMetrics;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let window = Window::last(Duration::days(30));
+/// let metrics = PullMetrics::fetch(&repo, &window, "<my user agent>", None).await?;
+///
+/// println!("Median time to merge: {:?}", metrics.time_to_merge().median());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PullMetrics {
+    opened: usize,
+    merged: usize,
+    closed_unmerged: usize,
+    time_to_merge: Distribution,
+    time_to_first_review: Distribution,
+}
+
+impl PullMetrics {
+    /// Computes metrics for the pull requests created in `window`.
+    ///
+    /// `reviews` maps pull request numbers to their reviews. Pull requests
+    /// without an entry are treated as not reviewed yet.
+    pub fn new(
+        pulls: &[PullRequest],
+        reviews: &HashMap<u64, Vec<Review>>,
+        window: &Window,
+    ) -> Self {
+        let pulls: Vec<_> = pulls
+            .iter()
+            .filter(|p| window.contains(p.created_at()))
+            .collect();
+
+        let merged = pulls.iter().filter(|p| p.merged()).count();
+        let closed_unmerged = pulls
+            .iter()
+            .filter(|p| p.closed_at().is_some() && !p.merged())
+            .count();
+
+        let time_to_merge = pulls
+            .iter()
+            .filter_map(|p| p.merged_at().map(|merged_at| merged_at - *p.created_at()))
+            .collect();
+
+        let time_to_first_review = pulls
+            .iter()
+            .filter_map(|p| {
+                let reviews = reviews.get(&p.number())?;
+                first_review(p, reviews).map(|reviewed_at| reviewed_at - *p.created_at())
+            })
+            .collect();
+
+        PullMetrics {
+            opened: pulls.len(),
+            merged,
+            closed_unmerged,
+            time_to_merge,
+            time_to_first_review,
+        }
+    }
+
+    /// Lists the repository's pull requests and the reviews of those created
+    /// in `window`, then computes metrics for them.
+    ///
+    /// Pull requests are listed newest first, and listing stops at the first
+    /// page that reaches past the start of `window`. Pull requests created
+    /// after the end of `window` are still listed, so a window far in the
+    /// past costs a page per 100 pull requests created since. This then
+    /// makes one request per pull request in `window`.
+    pub async fn fetch(
+        repo: &Repo,
+        window: &Window,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let list = repo
+            .pulls()
+            .state(StateFilter::All)
+            .sort(PullSort::Created)
+            .direction(Direction::Desc)
+            .authorized(authorization);
+        let pulls = list
+            .fetch_until(user_agent, |p| p.created_at() < window.start())
+            .await?;
+
+        let mut reviews = HashMap::new();
+        for pull in pulls.iter().filter(|p| window.contains(p.created_at())) {
+            let list = repo.pull_reviews(pull.number()).authorized(authorization);
+            reviews.insert(pull.number(), list.fetch(user_agent).await?);
+        }
+
+        Ok(PullMetrics::new(&pulls, &reviews, window))
+    }
+
+    /// Pull requests created in the window.
+    pub fn opened(&self) -> usize {
+        self.opened
+    }
+
+    pub fn merged(&self) -> usize {
+        self.merged
+    }
+
+    /// Pull requests that were closed without being merged.
+    pub fn closed_unmerged(&self) -> usize {
+        self.closed_unmerged
+    }
+
+    /// Fraction of closed pull requests that were merged.
+    ///
+    /// `None` if none were closed.
+    pub fn merge_rate(&self) -> Option<f64> {
+        let closed = self.merged + self.closed_unmerged;
+        if closed == 0 {
+            None
+        } else {
+            Some(self.merged as f64 / closed as f64)
+        }
+    }
+
+    /// From creation to merge, for merged pull requests.
+    pub fn time_to_merge(&self) -> &Distribution {
+        &self.time_to_merge
+    }
+
+    /// From creation to the first review by someone other than the author,
+    /// for reviewed pull requests.
+    pub fn time_to_first_review(&self) -> &Distribution {
+        &self.time_to_first_review
+    }
+}
+
+fn first_review(pull: &PullRequest, reviews: &[Review]) -> Option<DateTime<Utc>> {
+    reviews
+        .iter()
+        .filter(|r| r.state() != ReviewState::Pending)
+        .filter(|r| match r.user() {
+            Some(user) => user.login() != pull.user().login(),
+            None => true,
+        })
+        .filter_map(|r| *r.submitted_at())
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::pull::tests::{pull, review};
+
+    #[test]
+    fn pull_metrics() {
+        let pulls: Vec<PullRequest> = vec![
+            pull(1, "2020-01-01T00:00:00Z", Some("2020-01-02T00:00:00Z"), Some("2020-01-02T00:00:00Z")),
+            pull(2, "2020-01-02T00:00:00Z", Some("2020-01-06T00:00:00Z"), Some("2020-01-06T00:00:00Z")),
+            pull(3, "2020-01-03T00:00:00Z", Some("2020-01-04T00:00:00Z"), None),
+            pull(4, "2020-01-04T00:00:00Z", None, None),
+            // Outside of the window
+            pull(5, "2019-12-01T00:00:00Z", Some("2019-12-02T00:00:00Z"), Some("2019-12-02T00:00:00Z")),
+        ]
+        .into_iter()
+        .map(|p| serde_json::from_value(p).unwrap())
+        .collect();
+
+        let mut reviews = HashMap::new();
+        reviews.insert(1, vec![
+            // The author's own comment isn't a review.
+            review("octocat", "COMMENTED", Some("2020-01-01T01:00:00Z")),
+            review("hubot", "APPROVED", Some("2020-01-01T06:00:00Z")),
+        ]);
+        reviews.insert(2, vec![
+            review("hubot", "PENDING", None),
+            review("monalisa", "CHANGES_REQUESTED", Some("2020-01-02T02:00:00Z")),
+        ]);
+        let reviews = reviews
+            .into_iter()
+            .map(|(n, r)| (n, r.into_iter().map(|r| serde_json::from_value(r).unwrap()).collect()))
+            .collect();
+
+        let window = Window::new(
+            "2020-01-01T00:00:00Z".parse().unwrap(),
+            "2020-02-01T00:00:00Z".parse().unwrap(),
+        );
+        let metrics = PullMetrics::new(&pulls, &reviews, &window);
+        // Merged, but without `closed_at`
+        let unclosed: PullRequest = serde_json::from_value(
+            pull(6, "2020-01-05T00:00:00Z", None, Some("2020-01-06T00:00:00Z")),
+        )
+        .unwrap();
+        let unclosed = PullMetrics::new(&[unclosed], &HashMap::new(), &window);
+
+        assert_eq!(4, metrics.opened());
+        assert_eq!(2, metrics.merged());
+        assert_eq!(1, metrics.closed_unmerged());
+        assert_eq!(Some(2.0 / 3.0), metrics.merge_rate());
+        assert_eq!(Some(Duration::days(4)), metrics.time_to_merge().max());
+        assert_eq!(
+            vec![Duration::hours(2), Duration::hours(6)],
+            metrics.time_to_first_review().durations(),
+        );
+        assert_eq!(1, unclosed.merged());
+        assert_eq!(0, unclosed.closed_unmerged());
+    }
+}
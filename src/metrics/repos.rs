@@ -0,0 +1,170 @@
+//! Totals across a set of repositories, such as those of a user.
+
+use std::collections::BTreeMap;
+
+use crate::{Repo, Result, User};
+
+/// Stars, forks and languages of a set of repositories.
+///
+/// Forks are left out unless built [`with_forks`](RepoStats::with_forks),
+/// since their stars and languages mostly belong to the upstream repository.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::User;
+/// use github_stats::metrics::repos::RepoStats;
+///
+/// let user = User::new("octocat", "<my user agent>").await?;
+/// let stats = RepoStats::fetch(&user, "<my user agent>", None).await?;
+///
+/// println!("{} stars across {} repositories", stats.stars(), stats.repos());
+/// for (name, stars) in stats.most_starred(3) {
+///     println!("{}: {}", name, stars);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RepoStats {
+    stars: u64,
+    forks: u64,
+    archived: usize,
+    /// Full names and stars, most starred first.
+    by_stars: Vec<(String, u64)>,
+    languages: BTreeMap<String, usize>,
+}
+
+impl RepoStats {
+    /// Totals the repositories that aren't forks.
+    pub fn new(repos: &[Repo]) -> Self {
+        let sources: Vec<_> = repos.iter().filter(|r| !r.fork()).collect();
+        RepoStats::total(&sources)
+    }
+
+    /// Totals every repository, including forks.
+    pub fn with_forks(repos: &[Repo]) -> Self {
+        let repos: Vec<_> = repos.iter().collect();
+        RepoStats::total(&repos)
+    }
+
+    fn total(repos: &[&Repo]) -> Self {
+        let mut by_stars: Vec<_> = repos
+            .iter()
+            .map(|r| (String::from(r.full_name()), r.stargazers_count()))
+            .collect();
+        by_stars.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+        let mut languages = BTreeMap::new();
+        for language in repos.iter().filter_map(|r| r.language().as_ref()) {
+            *languages.entry(language.clone()).or_insert(0) += 1;
+        }
+        RepoStats {
+            stars: repos.iter().map(|r| r.stargazers_count()).sum(),
+            forks: repos.iter().map(|r| r.forks_count()).sum(),
+            archived: repos.iter().filter(|r| r.archived()).count(),
+            by_stars,
+            languages,
+        }
+    }
+
+    /// Lists the repositories `user` owns and totals those that aren't
+    /// forks.
+    pub async fn fetch(user: &User, user_agent: &str, authorization: Option<&str>) -> Result<Self> {
+        let repos = user
+            .repos()
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        Ok(RepoStats::new(&repos))
+    }
+
+    pub fn repos(&self) -> usize {
+        self.by_stars.len()
+    }
+
+    /// Total stars.
+    pub fn stars(&self) -> u64 {
+        self.stars
+    }
+
+    /// Total forks.
+    pub fn forks(&self) -> u64 {
+        self.forks
+    }
+
+    pub fn archived(&self) -> usize {
+        self.archived
+    }
+
+    /// Repositories that aren't archived.
+    pub fn active(&self) -> usize {
+        self.repos() - self.archived
+    }
+
+    /// Up to `n` full names and stars, most starred first.
+    pub fn most_starred(&self, n: usize) -> &[(String, u64)] {
+        &self.by_stars[..n.min(self.by_stars.len())]
+    }
+
+    /// Number of repositories per primary language. Repositories without a
+    /// language are left out.
+    pub fn languages(&self) -> &BTreeMap<String, usize> {
+        &self.languages
+    }
+
+    /// Share of the repositories with a language that use each language,
+    /// from `0` to `1`.
+    pub fn language_share(&self) -> BTreeMap<&str, f64> {
+        let total: usize = self.languages.values().sum();
+        self.languages
+            .iter()
+            .map(|(language, count)| (language.as_str(), *count as f64 / total as f64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    #[test]
+    fn repo_stats() {
+        let repos = vec![
+            repo(json!({"full_name": "octocat/a", "stargazers_count": 10, "forks_count": 2, "language": "Rust"})),
+            repo(json!({"full_name": "octocat/b", "stargazers_count": 50, "forks_count": 0, "language": "Rust"})),
+            repo(json!({"full_name": "octocat/c", "stargazers_count": 5, "forks_count": 1, "language": "Go", "archived": true})),
+            repo(json!({"full_name": "octocat/d", "stargazers_count": 0, "forks_count": 0, "language": null})),
+        ];
+        let stats = RepoStats::new(&repos);
+        let top: Vec<_> = stats.most_starred(2).iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(65, stats.stars());
+        assert_eq!(3, stats.forks());
+        assert_eq!(1, stats.archived());
+        assert_eq!(3, stats.active());
+        assert_eq!(vec!["octocat/b", "octocat/a"], top);
+        assert_eq!(4, stats.most_starred(10).len());
+        assert_eq!(Some(&2), stats.languages().get("Rust"));
+        assert_eq!(Some(&(1.0 / 3.0)), stats.language_share().get("Go"));
+    }
+
+    #[test]
+    fn forks_left_out() {
+        let repos = vec![
+            repo(json!({"full_name": "octocat/a", "stargazers_count": 10, "language": "Rust"})),
+            repo(json!({"full_name": "octocat/rust", "stargazers_count": 90000, "fork": true, "language": "Rust"})),
+        ];
+        let stats = RepoStats::new(&repos);
+        let with_forks = RepoStats::with_forks(&repos);
+
+        assert_eq!(1, stats.repos());
+        assert_eq!(10, stats.stars());
+        assert_eq!(Some(&1), stats.languages().get("Rust"));
+        assert_eq!(2, with_forks.repos());
+        assert_eq!(90010, with_forks.stars());
+    }
+}
@@ -0,0 +1,193 @@
+//! For getting milestones and how close they are to completion.
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::issue::{State, StateFilter};
+use crate::request::{Direction, List};
+use crate::User;
+
+/// A milestone that issues and pull requests can be added to.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Milestone {
+    id: u64,
+    node_id: String,
+    number: u64,
+    url: String,
+    html_url: String,
+    title: String,
+    description: Option<String>,
+    state: State,
+    /// `None` if the account was deleted.
+    creator: Option<User>,
+    open_issues: u64,
+    closed_issues: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    due_on: Option<DateTime<Utc>>,
+}
+
+/// Order of milestones returned by
+/// [`Repo::milestones`](crate::Repo::milestones).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MilestoneSort {
+    DueOn,
+    Completeness,
+}
+
+impl fmt::Display for MilestoneSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use MilestoneSort::*;
+        write!(f, "{}", match self {
+            DueOn => "due_on",
+            Completeness => "completeness",
+        })
+    }
+}
+
+impl Milestone {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// `None` if the account was deleted.
+    pub fn creator(&self) -> &Option<User> {
+        &self.creator
+    }
+
+    /// Issues + PRs
+    pub fn open_issues(&self) -> u64 {
+        self.open_issues
+    }
+
+    /// Issues + PRs
+    pub fn closed_issues(&self) -> u64 {
+        self.closed_issues
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn closed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.closed_at
+    }
+
+    pub fn due_on(&self) -> &Option<DateTime<Utc>> {
+        &self.due_on
+    }
+
+    /// Percentage of issues that are closed, from `0.0` to `100.0`.
+    ///
+    /// `None` if the milestone has no issues.
+    pub fn completion(&self) -> Option<f64> {
+        let total = self.open_issues + self.closed_issues;
+        if total == 0 {
+            None
+        } else {
+            Some(self.closed_issues as f64 / total as f64 * 100.0)
+        }
+    }
+
+    /// If the milestone is still open after its due date.
+    pub fn is_overdue(&self, now: &DateTime<Utc>) -> bool {
+        self.state == State::Open && self.due_on.is_some_and(|due_on| &due_on < now)
+    }
+}
+
+/// Filters for [`Repo::milestones`](crate::Repo::milestones).
+impl List<Milestone> {
+    /// Defaults to [`StateFilter::Open`].
+    pub fn state(self, state: StateFilter) -> Self {
+        self.param("state", state)
+    }
+
+    /// Defaults to [`MilestoneSort::DueOn`].
+    pub fn sort(self, sort: MilestoneSort) -> Self {
+        self.param("sort", sort)
+    }
+
+    /// Defaults to [`Direction::Asc`].
+    pub fn direction(self, direction: Direction) -> Self {
+        self.param("direction", direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::user::tests::user;
+
+    fn milestone(open_issues: u64, closed_issues: u64, due_on: Option<&str>) -> Milestone {
+        serde_json::from_value(json!({
+            "id": 1002604,
+            "node_id": "MDk6TWlsZXN0b25lMTAwMjYwNA==",
+            "number": 1,
+            "url": "https://api.github.com/repos/octocat/Hello-World/milestones/1",
+            "html_url": "https://github.com/octocat/Hello-World/milestones/v1.0",
+            "title": "v1.0",
+            "description": "Tracking milestone for version 1.0",
+            "state": "open",
+            "creator": user("octocat"),
+            "open_issues": open_issues,
+            "closed_issues": closed_issues,
+            "created_at": "2011-04-10T20:09:31Z",
+            "updated_at": "2014-03-03T18:58:10Z",
+            "closed_at": null,
+            "due_on": due_on
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn milestone_completion() {
+        assert_eq!(Some(75.0), milestone(1, 3, None).completion());
+        assert_eq!(None, milestone(0, 0, None).completion());
+    }
+
+    #[test]
+    fn overdue_milestone() {
+        let now = "2012-10-09T23:39:01Z".parse().unwrap();
+
+        assert!(milestone(1, 3, Some("2012-10-09T23:39:00Z")).is_overdue(&now));
+        assert!(!milestone(1, 3, Some("2012-10-10T00:00:00Z")).is_overdue(&now));
+        assert!(!milestone(1, 3, None).is_overdue(&now));
+    }
+}
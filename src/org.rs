@@ -0,0 +1,44 @@
+//! For getting the repositories and security alerts of an organization.
+
+use crate::alert::{CodeScanningAlert, DependabotAlert, SecretScanningAlert};
+use crate::request::List;
+use crate::RepoList;
+
+const URL: &str = "https://api.github.com/orgs";
+
+/// Lists the repositories of the organization `org`.
+///
+/// Private repositories are only listed with an authorization token that
+/// can see them.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::org;
+///
+/// let repos = org::repos("rust-lang").fetch("<my user agent>").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn repos(org: &str) -> RepoList {
+    RepoList::new(format!("{}/{}/repos", URL, org))
+}
+
+/// Lists the Dependabot alerts of every repository of `org`, which needs an
+/// authorization token of an owner or security manager.
+pub fn dependabot_alerts(org: &str) -> List<DependabotAlert> {
+    List::new(format!("{}/{}/dependabot/alerts", URL, org))
+}
+
+/// Lists the code scanning alerts of every repository of `org`, which needs
+/// an authorization token of an owner or security manager.
+pub fn code_scanning_alerts(org: &str) -> List<CodeScanningAlert> {
+    List::new(format!("{}/{}/code-scanning/alerts", URL, org))
+}
+
+/// Lists the secret scanning alerts of every repository of `org`, which
+/// needs an authorization token of an owner or security manager.
+pub fn secret_scanning_alerts(org: &str) -> List<SecretScanningAlert> {
+    List::new(format!("{}/{}/secret-scanning/alerts", URL, org))
+}
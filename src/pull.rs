@@ -0,0 +1,382 @@
+//! For getting pull requests and their reviews.
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::request::{Direction, List};
+use crate::User;
+
+pub use crate::issue::{State, StateFilter};
+
+/// A pull request as [Github] reports it.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PullRequest {
+    id: u64,
+    node_id: String,
+    number: u64,
+    url: String,
+    html_url: String,
+    state: State,
+    title: String,
+    body: Option<String>,
+    user: User,
+    #[serde(default)]
+    draft: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+    head: PullRef,
+    base: PullRef,
+    author_association: String,
+    /// Only included when a single pull request is requested.
+    #[serde(default)]
+    additions: Option<u64>,
+    /// Only included when a single pull request is requested.
+    #[serde(default)]
+    deletions: Option<u64>,
+    /// Only included when a single pull request is requested.
+    #[serde(default)]
+    changed_files: Option<u64>,
+    /// Only included when a single pull request is requested.
+    #[serde(default)]
+    commits: Option<u64>,
+}
+
+/// The head or base branch of a [`PullRequest`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PullRef {
+    label: String,
+    r#ref: String,
+    sha: String,
+    /// `None` if the account was deleted.
+    user: Option<User>,
+}
+
+/// A review of a [`PullRequest`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Review {
+    id: u64,
+    node_id: String,
+    /// `None` if the account was deleted.
+    user: Option<User>,
+    body: Option<String>,
+    state: ReviewState,
+    html_url: String,
+    /// `None` while the review is pending.
+    submitted_at: Option<DateTime<Utc>>,
+    author_association: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+    Pending,
+}
+
+/// Order of pull requests returned by [`Repo::pulls`](crate::Repo::pulls).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PullSort {
+    Created,
+    Updated,
+    /// By number of comments.
+    Popularity,
+    /// By age, filtering out pull requests updated in the last month.
+    LongRunning,
+}
+
+impl fmt::Display for PullSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PullSort::*;
+        write!(f, "{}", match self {
+            Created => "created",
+            Updated => "updated",
+            Popularity => "popularity",
+            LongRunning => "long-running",
+        })
+    }
+}
+
+impl PullRequest {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn body(&self) -> &Option<String> {
+        &self.body
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn draft(&self) -> bool {
+        self.draft
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn closed_at(&self) -> &Option<DateTime<Utc>> {
+        &self.closed_at
+    }
+
+    pub fn merged_at(&self) -> &Option<DateTime<Utc>> {
+        &self.merged_at
+    }
+
+    pub fn merged(&self) -> bool {
+        self.merged_at.is_some()
+    }
+
+    pub fn merge_commit_sha(&self) -> &Option<String> {
+        &self.merge_commit_sha
+    }
+
+    pub fn head(&self) -> &PullRef {
+        &self.head
+    }
+
+    pub fn base(&self) -> &PullRef {
+        &self.base
+    }
+
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+
+    /// Only included when a single pull request is requested.
+    pub fn additions(&self) -> Option<u64> {
+        self.additions
+    }
+
+    /// Only included when a single pull request is requested.
+    pub fn deletions(&self) -> Option<u64> {
+        self.deletions
+    }
+
+    /// Only included when a single pull request is requested.
+    pub fn changed_files(&self) -> Option<u64> {
+        self.changed_files
+    }
+
+    /// Only included when a single pull request is requested.
+    pub fn commits(&self) -> Option<u64> {
+        self.commits
+    }
+}
+
+impl PullRef {
+    /// `user:branch`
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// *Use `r#ref` to avoid conflict with `ref` keyword.*
+    pub fn r#ref(&self) -> &str {
+        &self.r#ref
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// `None` if the account was deleted.
+    pub fn user(&self) -> &Option<User> {
+        &self.user
+    }
+}
+
+impl Review {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// `None` if the account was deleted.
+    pub fn user(&self) -> &Option<User> {
+        &self.user
+    }
+
+    pub fn body(&self) -> &Option<String> {
+        &self.body
+    }
+
+    pub fn state(&self) -> ReviewState {
+        self.state
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    /// `None` while the review is pending.
+    pub fn submitted_at(&self) -> &Option<DateTime<Utc>> {
+        &self.submitted_at
+    }
+
+    pub fn author_association(&self) -> &str {
+        &self.author_association
+    }
+}
+
+/// Filters for [`Repo::pulls`](crate::Repo::pulls).
+impl List<PullRequest> {
+    /// Defaults to [`StateFilter::Open`].
+    pub fn state(self, state: StateFilter) -> Self {
+        self.param("state", state)
+    }
+
+    /// Only pull requests into this branch.
+    pub fn base(self, branch: &str) -> Self {
+        self.param("base", branch)
+    }
+
+    /// Only pull requests from this branch, written as `user:branch`.
+    pub fn head(self, label: &str) -> Self {
+        self.param("head", label)
+    }
+
+    /// Defaults to [`PullSort::Created`].
+    pub fn sort(self, sort: PullSort) -> Self {
+        self.param("sort", sort)
+    }
+
+    /// Defaults to [`Direction::Desc`], unless sorting by
+    /// [`PullSort::LongRunning`].
+    pub fn direction(self, direction: Direction) -> Self {
+        self.param("direction", direction)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::repository::tests::repo;
+    use crate::user::tests::user;
+
+    /// A pull request by `octocat`, as [Github] lists it.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn pull(
+        number: u64,
+        created_at: &str,
+        closed_at: Option<&str>,
+        merged_at: Option<&str>,
+    ) -> Value {
+        json!({
+            "id": number,
+            "node_id": "MDExOlB1bGxSZXF1ZXN0MQ==",
+            "number": number,
+            "url": format!("https://api.github.com/repos/octocat/Hello-World/pulls/{}", number),
+            "html_url": format!("https://github.com/octocat/Hello-World/pull/{}", number),
+            "state": if closed_at.is_some() { "closed" } else { "open" },
+            "title": "Amazing new feature",
+            "body": null,
+            "user": user("octocat"),
+            "draft": false,
+            "created_at": created_at,
+            "updated_at": closed_at.unwrap_or(created_at),
+            "closed_at": closed_at,
+            "merged_at": merged_at,
+            "merge_commit_sha": null,
+            "head": {
+                "label": "octocat:new-topic",
+                "ref": "new-topic",
+                "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "user": user("octocat")
+            },
+            "base": {
+                "label": "octocat:master",
+                "ref": "master",
+                "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "user": user("octocat")
+            },
+            "author_association": "CONTRIBUTOR"
+        })
+    }
+
+    /// A review submitted by `login`.
+    pub(crate) fn review(login: &str, state: &str, submitted_at: Option<&str>) -> Value {
+        json!({
+            "id": 80,
+            "node_id": "MDE3OlB1bGxSZXF1ZXN0UmV2aWV3ODA=",
+            "user": user(login),
+            "body": "Here is the body for the review.",
+            "state": state,
+            "html_url": "https://github.com/octocat/Hello-World/pull/12#pullrequestreview-80",
+            "submitted_at": submitted_at,
+            "author_association": "MEMBER"
+        })
+    }
+
+    #[test]
+    fn built_pulls() {
+        const EXPECTED: &str = "https://api.github.com/repos/octocat/Hello-World/pulls?state=closed&base=master&sort=long-running&per_page=100";
+        let pulls = repo(json!({}))
+            .pulls()
+            .state(StateFilter::Closed)
+            .base("master")
+            .sort(PullSort::LongRunning);
+
+        assert_eq!(EXPECTED, pulls.to_string());
+    }
+
+    #[test]
+    fn listed_pull() {
+        let pull: PullRequest = serde_json::from_value(pull(
+            1347,
+            "2011-01-26T19:01:12Z",
+            Some("2011-01-27T19:01:12Z"),
+            Some("2011-01-27T19:01:12Z"),
+        ))
+        .unwrap();
+
+        assert_eq!(State::Closed, pull.state());
+        assert!(pull.merged());
+        assert_eq!(None, pull.additions());
+    }
+}
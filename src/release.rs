@@ -0,0 +1,290 @@
+//! For getting releases and how often their assets were downloaded.
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{glob, User};
+
+/// A published (or draft) release of a repository.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Release {
+    id: u64,
+    node_id: String,
+    url: String,
+    html_url: String,
+    tag_name: String,
+    target_commitish: String,
+    name: Option<String>,
+    body: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    created_at: DateTime<Utc>,
+    /// `None` for drafts.
+    published_at: Option<DateTime<Utc>>,
+    author: User,
+    assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a [`Release`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReleaseAsset {
+    id: u64,
+    node_id: String,
+    url: String,
+    browser_download_url: String,
+    name: String,
+    label: Option<String>,
+    state: String,
+    content_type: String,
+    /// In bytes.
+    size: u64,
+    download_count: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Release {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn html_url(&self) -> &str {
+        &self.html_url
+    }
+
+    pub fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    pub fn target_commitish(&self) -> &str {
+        &self.target_commitish
+    }
+
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    pub fn body(&self) -> &Option<String> {
+        &self.body
+    }
+
+    pub fn draft(&self) -> bool {
+        self.draft
+    }
+
+    pub fn prerelease(&self) -> bool {
+        self.prerelease
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    /// `None` for drafts.
+    pub fn published_at(&self) -> &Option<DateTime<Utc>> {
+        &self.published_at
+    }
+
+    pub fn author(&self) -> &User {
+        &self.author
+    }
+
+    pub fn assets(&self) -> &[ReleaseAsset] {
+        &self.assets
+    }
+
+    /// Downloads of every asset in the release.
+    pub fn total_downloads(&self) -> u64 {
+        self.assets.iter().map(ReleaseAsset::download_count).sum()
+    }
+
+    /// Downloads of assets with names matching `pattern`.
+    ///
+    /// `*` matches any run of characters, and `?` matches a single
+    /// character. For example, `*.tar.gz` or `app-*-linux*`.
+    pub fn downloads_matching(&self, pattern: &str) -> u64 {
+        self.assets
+            .iter()
+            .filter(|a| glob::matches(pattern, &a.name))
+            .map(ReleaseAsset::download_count)
+            .sum()
+    }
+}
+
+impl ReleaseAsset {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn browser_download_url(&self) -> &str {
+        &self.browser_download_url
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn label(&self) -> &Option<String> {
+        &self.label
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// In bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn download_count(&self) -> u64 {
+        self.download_count
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
+
+/// Downloads of every asset in every release.
+pub fn total_downloads(releases: &[Release]) -> u64 {
+    releases.iter().map(Release::total_downloads).sum()
+}
+
+/// Downloads of assets with names matching `pattern`, across every release.
+///
+/// See [`Release::downloads_matching`].
+pub fn downloads_matching(releases: &[Release], pattern: &str) -> u64 {
+    releases.iter().map(|r| r.downloads_matching(pattern)).sum()
+}
+
+/// Cumulative downloads by publish date, oldest first.
+///
+/// [Github] only reports each asset's total downloads, so every download is
+/// counted at the date its release was published. Drafts are skipped.
+///
+/// [Github]: https://github.com/
+pub fn downloads_over_time(releases: &[Release]) -> Vec<(DateTime<Utc>, u64)> {
+    let mut published: Vec<_> = releases
+        .iter()
+        .filter_map(|r| r.published_at.map(|date| (date, r.total_downloads())))
+        .collect();
+    published.sort_by_key(|(date, _)| *date);
+
+    let mut total = 0;
+    published
+        .into_iter()
+        .map(|(date, downloads)| {
+            total += downloads;
+            (date, total)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::user::tests::user;
+
+    fn asset(name: &str, download_count: u64) -> Value {
+        json!({
+            "id": 1,
+            "node_id": "MDEyOlJlbGVhc2VBc3NldDE=",
+            "url": "https://api.github.com/repos/octocat/Hello-World/releases/assets/1",
+            "browser_download_url": "https://github.com/octocat/Hello-World/releases/download/v1.0.0/example.zip",
+            "name": name,
+            "label": null,
+            "state": "uploaded",
+            "content_type": "application/octet-stream",
+            "size": 1024,
+            "download_count": download_count,
+            "created_at": "2013-02-27T19:35:32Z",
+            "updated_at": "2013-02-27T19:35:32Z",
+        })
+    }
+
+    fn release(tag_name: &str, published_at: Option<&str>, assets: Vec<Value>) -> Release {
+        serde_json::from_value(json!({
+            "id": 1,
+            "node_id": "MDc6UmVsZWFzZTE=",
+            "url": "https://api.github.com/repos/octocat/Hello-World/releases/1",
+            "html_url": "https://github.com/octocat/Hello-World/releases/v1.0.0",
+            "tag_name": tag_name,
+            "target_commitish": "master",
+            "name": tag_name,
+            "body": null,
+            "draft": published_at.is_none(),
+            "prerelease": false,
+            "created_at": "2013-02-27T19:35:32Z",
+            "published_at": published_at,
+            "author": user("octocat"),
+            "assets": assets,
+        }))
+        .unwrap()
+    }
+
+    fn releases() -> Vec<Release> {
+        vec![
+            release("v2.0.0", Some("2020-02-01T00:00:00Z"), vec![
+                asset("app-linux.tar.gz", 20),
+                asset("app-windows.zip", 5),
+            ]),
+            release("v1.0.0", Some("2020-01-01T00:00:00Z"), vec![
+                asset("app-linux.tar.gz", 10),
+            ]),
+            release("v3.0.0", None, vec![asset("app-linux.tar.gz", 0)]),
+        ]
+    }
+
+    #[test]
+    fn downloads_per_release() {
+        let releases = releases();
+
+        assert_eq!(25, releases[0].total_downloads());
+        assert_eq!(35, total_downloads(&releases));
+    }
+
+    #[test]
+    fn downloads_per_pattern() {
+        let releases = releases();
+
+        assert_eq!(30, downloads_matching(&releases, "*.tar.gz"));
+        assert_eq!(5, downloads_matching(&releases, "*windows*"));
+    }
+
+    #[test]
+    fn cumulative_downloads() {
+        let history: Vec<u64> = downloads_over_time(&releases())
+            .into_iter()
+            .map(|(_, n)| n)
+            .collect();
+
+        assert_eq!(vec![10, 35], history);
+    }
+}
@@ -1,14 +1,95 @@
 //! For getting repository information.
 
-use chrono::prelude::{DateTime, Utc};
-use serde::Deserialize;
+use std::fmt;
 
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{Job, Workflow, WorkflowRun};
+use crate::alert::{CodeScanningAlert, DependabotAlert, SecretScanningAlert};
+use crate::check::{CheckRun, CheckSuite, CombinedStatus};
+use crate::collaborator::{Collaborator, Team};
+use crate::commit::Commit;
+use crate::community::CommunityProfile;
+use crate::content::{self, Contents, File, RawFile};
+use crate::compare::Comparison;
+use crate::deployment::{Deployment, DeploymentStatus, Environment};
+use crate::event::Event;
+use crate::fork::ForkSort;
+use crate::issue::{Issue, IssueComment, IssueEvent};
+use crate::label::Label;
+use crate::milestone::Milestone;
+use crate::pull::{PullRequest, Review};
+use crate::release::Release;
+use crate::sbom::Sbom;
+use crate::snapshot::{RepoSnapshot, SnapshotDiff};
+use crate::request::{Direction, List, Request};
+use crate::stargazer::{self, Stargazer};
+use crate::tree::Tree;
 use crate::{Result, User};
 
+/// Which repositories [`User::repos`] and [`org::repos`] list.
+///
+/// [`org::repos`]: crate::org::repos
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepoType {
+    All,
+    /// The default for a user. Only for users.
+    Owner,
+    /// Repositories the user or organization is a member of, but doesn't own.
+    Member,
+    /// Only for organizations.
+    Public,
+    /// Only for organizations.
+    Private,
+    /// Only for organizations.
+    Forks,
+    /// Repositories that aren't forks. Only for organizations.
+    Sources,
+}
+
+/// Order of the repositories listed by [`User::repos`] and [`org::repos`].
+///
+/// [`org::repos`]: crate::org::repos
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepoSort {
+    Created,
+    Updated,
+    Pushed,
+    FullName,
+}
+
+impl fmt::Display for RepoType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use RepoType::*;
+        write!(f, "{}", match self {
+            All => "all",
+            Owner => "owner",
+            Member => "member",
+            Public => "public",
+            Private => "private",
+            Forks => "forks",
+            Sources => "sources",
+        })
+    }
+}
+
+impl fmt::Display for RepoSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use RepoSort::*;
+        write!(f, "{}", match self {
+            Created => "created",
+            Updated => "updated",
+            Pushed => "pushed",
+            FullName => "full_name",
+        })
+    }
+}
+
 /// Represents that stats of a [Github] repository.
 ///
 /// [Github]: https://github.com/
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Repo {
     id: u64,
     node_id: String,
@@ -17,7 +98,7 @@ pub struct Repo {
     private: bool,
     owner: User,
     html_url: String,
-    description: String,
+    description: Option<String>,
     fork: bool,
     url: String,
     created_at: DateTime<Utc>,
@@ -27,7 +108,7 @@ pub struct Repo {
     ssh_url: String,
     clone_url: String,
     svn_url: String,
-    homepage: String,
+    homepage: Option<String>,
     /// In *kilo*bytes.
     size: u64,
     stargazers_count: u64,
@@ -42,7 +123,10 @@ pub struct Repo {
     open_issues: u64,
     default_branch: String,
     /// Number of watchers.
-    subscribers_count: u64,
+    ///
+    /// Not included when repositories are listed, such as by [`Repo::forks`].
+    #[serde(default)]
+    subscribers_count: Option<u64>,
     has_issues: bool,
     has_wiki: bool,
     open_issues_count: u64,
@@ -62,7 +146,7 @@ impl Repo {
         let repo: Repo = reqwest::Client::builder()
             .user_agent(user_agent)
             .build()?
-            .get(&repo_api_url(user, repo))
+            .get(repo_api_url(user, repo))
             .send()
             .await?
             .json()
@@ -99,7 +183,7 @@ impl Repo {
         &self.html_url
     }
 
-    pub fn description(&self) -> &str {
+    pub fn description(&self) -> &Option<String> {
         &self.description
     }
 
@@ -139,7 +223,7 @@ impl Repo {
         &self.svn_url
     }
 
-    pub fn homepage(&self) -> &str {
+    pub fn homepage(&self) -> &Option<String> {
         &self.homepage
     }
 
@@ -190,7 +274,10 @@ impl Repo {
     }
 
     /// Number of watchers.
-    pub fn subscribers_count(&self) -> u64 {
+    ///
+    /// `None` when the repository came from a list, such as
+    /// [`Repo::forks`].
+    pub fn subscribers_count(&self) -> Option<u64> {
         self.subscribers_count
     }
 
@@ -205,6 +292,419 @@ impl Repo {
     pub fn open_issues_count(&self) -> u64 {
         self.open_issues_count
     }
+
+    /// What changed from this repository to `newer`, such as a rename or a
+    /// few more stars.
+    ///
+    /// Dated by when each was last updated. Compare
+    /// [snapshots](RepoSnapshot::diff) to date by when they were fetched.
+    /// Fails if `newer` is a different repository.
+    pub fn diff(&self, newer: &Repo) -> Result<SnapshotDiff> {
+        let old = RepoSnapshot::new(self, self.updated_at);
+        old.diff(&RepoSnapshot::new(newer, newer.updated_at))
+    }
+
+    /// Lists who starred the repository, and when.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::Repo;
+    ///
+    /// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+    /// let stargazers = repo
+    ///     .stargazers_with_dates()
+    ///     .fetch("<my user agent>")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stargazers_with_dates(&self) -> List<Stargazer> {
+        List::new(format!("{}/stargazers", self.url)).accept(stargazer::MEDIA_TYPE)
+    }
+
+    /// Lists the repository's direct forks.
+    ///
+    /// See [`ForkTree`](crate::fork::ForkTree) for forks of forks.
+    pub fn forks(&self, sort: ForkSort) -> List<Repo> {
+        List::new(format!("{}/forks", self.url)).param("sort", sort)
+    }
+
+    /// Lists the repository's releases, newest first.
+    ///
+    /// Drafts are only included when authorized to push to the repository.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::{release, Repo};
+    ///
+    /// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+    /// let releases = repo.releases().fetch("<my user agent>").await?;
+    ///
+    /// println!("Total downloads: {}", release::total_downloads(&releases));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn releases(&self) -> List<Release> {
+        List::new(format!("{}/releases", self.url))
+    }
+
+    /// Gets the latest published full release.
+    ///
+    /// Drafts and prereleases are never the latest release.
+    pub fn latest_release(&self) -> Request<Release> {
+        Request::new(format!("{}/releases/latest", self.url))
+    }
+
+    /// Lists commits, newest first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::Repo;
+    ///
+    /// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+    /// let commits = repo
+    ///     .commits()
+    ///     .path("README.md")
+    ///     .author("spenserblack")
+    ///     .fetch("<my user agent>")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn commits(&self) -> List<Commit> {
+        List::new(format!("{}/commits", self.url))
+    }
+
+    /// Counts the commits on the default branch without downloading them.
+    ///
+    /// Use [`List::count`] on [`Repo::commits`] to count with filters.
+    pub async fn commit_count(&self, user_agent: &str) -> Result<u64> {
+        self.commits().count(user_agent).await
+    }
+
+    /// Gets a single commit by its SHA, or by a branch or tag name.
+    pub fn commit(&self, sha: &str) -> Request<Commit> {
+        Request::new(format!("{}/commits/{}", self.url, sha))
+    }
+
+    /// Compares `head` to `base`, which can each be a branch, tag or SHA.
+    ///
+    /// To compare across forks, use `user:branch`.
+    pub fn compare(&self, base: &str, head: &str) -> Request<Comparison> {
+        Request::new(format!("{}/compare/{}...{}", self.url, base, head))
+    }
+
+    /// Gets the latest status for each context of `r#ref`, which can be a
+    /// branch, tag or SHA.
+    ///
+    /// Includes up to 100 statuses.
+    pub fn combined_status(&self, r#ref: &str) -> Request<CombinedStatus> {
+        Request::new(format!("{}/commits/{}/status", self.url, r#ref)).param("per_page", 100)
+    }
+
+    /// Lists the check runs of `r#ref`, which can be a branch, tag or SHA.
+    pub fn check_runs(&self, r#ref: &str) -> List<CheckRun> {
+        List::new(format!("{}/commits/{}/check-runs", self.url, r#ref)).items_key("check_runs")
+    }
+
+    /// Lists the check suites of `r#ref`, which can be a branch, tag or SHA.
+    pub fn check_suites(&self, r#ref: &str) -> List<CheckSuite> {
+        List::new(format!("{}/commits/{}/check-suites", self.url, r#ref)).items_key("check_suites")
+    }
+
+    /// Gets [Github]'s community profile, which only public repositories have.
+    ///
+    /// Use [`RepoHealth`](crate::community::RepoHealth) to also check for a
+    /// security policy.
+    ///
+    /// [Github]: https://github.com/
+    pub fn community_profile(&self) -> Request<CommunityProfile> {
+        Request::new(format!("{}/community/profile", self.url))
+    }
+
+    /// Gets the README of the default branch.
+    pub fn readme(&self) -> Request<File> {
+        Request::new(format!("{}/readme", self.url))
+    }
+
+    /// Gets the file or directory at `path` in `r#ref`, which can be a
+    /// branch, tag or SHA.
+    ///
+    /// Files larger than 1 MB are listed without their content. Use
+    /// [`Repo::raw_file`] for those.
+    pub fn contents(&self, path: &str, r#ref: &str) -> Request<Contents> {
+        Request::new(format!("{}/contents/{}", self.url, path.trim_start_matches('/')))
+            .param("ref", r#ref)
+    }
+
+    /// Gets the file at `path` in `r#ref` as is, up to 100 MB.
+    pub fn raw_file(&self, path: &str, r#ref: &str) -> Request<RawFile> {
+        Request::new(format!("{}/contents/{}", self.url, path.trim_start_matches('/')))
+            .param("ref", r#ref)
+            .accept(content::RAW_MEDIA_TYPE)
+    }
+
+    /// Gets every path in `r#ref`, which can be a branch, tag, commit SHA or
+    /// tree SHA.
+    ///
+    /// [Github] truncates large trees. Use
+    /// [`Tree::fetch_complete`](crate::tree::Tree::fetch_complete) to get
+    /// those.
+    ///
+    /// [Github]: https://github.com/
+    pub fn tree(&self, r#ref: &str) -> Request<Tree> {
+        Request::new(format!("{}/git/trees/{}", self.url, r#ref)).param("recursive", 1)
+    }
+
+    /// Lists the public events of the repository, newest first.
+    ///
+    /// [Github] only lists the latest 300 events from the last 90 days.
+    ///
+    /// [Github]: https://github.com/
+    pub fn events(&self) -> List<Event> {
+        List::new(format!("{}/events", self.url))
+    }
+
+    /// Lists the users who can access the repository, with their
+    /// permissions.
+    ///
+    /// Needs an authorization token with push access.
+    pub fn collaborators(&self) -> List<Collaborator> {
+        List::new(format!("{}/collaborators", self.url))
+    }
+
+    /// Lists the teams that can access the repository.
+    pub fn teams(&self) -> List<Team> {
+        List::new(format!("{}/teams", self.url))
+    }
+
+    /// Lists deployments, newest first.
+    pub fn deployments(&self) -> List<Deployment> {
+        List::new(format!("{}/deployments", self.url))
+    }
+
+    /// Lists the statuses of a deployment, newest first.
+    pub fn deployment_statuses(&self, id: u64) -> List<DeploymentStatus> {
+        List::new(format!("{}/deployments/{}/statuses", self.url, id))
+    }
+
+    /// Lists the environments that can be deployed to.
+    pub fn environments(&self) -> List<Environment> {
+        List::new(format!("{}/environments", self.url)).items_key("environments")
+    }
+
+    /// Gets the software bill of materials from the dependency graph.
+    pub fn sbom(&self) -> Request<Sbom> {
+        Request::new(format!("{}/dependency-graph/sbom", self.url)).key("sbom")
+    }
+
+    /// Lists Dependabot alerts, which needs an authorization token with
+    /// access to them.
+    pub fn dependabot_alerts(&self) -> List<DependabotAlert> {
+        List::new(format!("{}/dependabot/alerts", self.url))
+    }
+
+    /// Lists code scanning alerts, which needs an authorization token with
+    /// access to them.
+    pub fn code_scanning_alerts(&self) -> List<CodeScanningAlert> {
+        List::new(format!("{}/code-scanning/alerts", self.url))
+    }
+
+    /// Lists secret scanning alerts, which needs an authorization token with
+    /// access to them.
+    pub fn secret_scanning_alerts(&self) -> List<SecretScanningAlert> {
+        List::new(format!("{}/secret-scanning/alerts", self.url))
+    }
+
+    /// Lists issues, newest first.
+    ///
+    /// Only open issues are listed unless filtered by
+    /// [`state`](List::state). Pull requests are included, since [Github]
+    /// considers them issues.
+    ///
+    /// [Github]: https://github.com/
+    pub fn issues(&self) -> List<Issue> {
+        List::new(format!("{}/issues", self.url))
+    }
+
+    /// Lists the comments on an issue or pull request, oldest first.
+    pub fn issue_comments(&self, number: u64) -> List<IssueComment> {
+        List::new(format!("{}/issues/{}/comments", self.url, number))
+    }
+
+    /// Lists the events of an issue or pull request, oldest first.
+    pub fn issue_events(&self, number: u64) -> List<IssueEvent> {
+        List::new(format!("{}/issues/{}/events", self.url, number))
+    }
+
+    /// Lists the labels that can be added to issues and pull requests.
+    pub fn labels(&self) -> List<Label> {
+        List::new(format!("{}/labels", self.url))
+    }
+
+    /// Lists milestones, soonest due first.
+    ///
+    /// Only open milestones are listed unless filtered by
+    /// [`state`](List::state).
+    pub fn milestones(&self) -> List<Milestone> {
+        List::new(format!("{}/milestones", self.url))
+    }
+
+    /// Lists pull requests, newest first.
+    ///
+    /// Only open pull requests are listed unless filtered by
+    /// [`state`](List::state).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::Repo;
+    /// use github_stats::pull::StateFilter;
+    ///
+    /// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+    /// let pulls = repo
+    ///     .pulls()
+    ///     .state(StateFilter::Closed)
+    ///     .base("master")
+    ///     .fetch("<my user agent>")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pulls(&self) -> List<PullRequest> {
+        List::new(format!("{}/pulls", self.url))
+    }
+
+    /// Gets a single pull request, including its additions, deletions and
+    /// changed files.
+    pub fn pull(&self, number: u64) -> Request<PullRequest> {
+        Request::new(format!("{}/pulls/{}", self.url, number))
+    }
+
+    /// Lists the reviews of a pull request, oldest first.
+    pub fn pull_reviews(&self, number: u64) -> List<Review> {
+        List::new(format!("{}/pulls/{}/reviews", self.url, number))
+    }
+
+    /// Lists the repository's [Github Actions] workflows.
+    ///
+    /// [Github Actions]: https://docs.github.com/en/actions
+    pub fn workflows(&self) -> List<Workflow> {
+        List::new(format!("{}/actions/workflows", self.url)).items_key("workflows")
+    }
+
+    /// Lists runs of every workflow, newest first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::Repo;
+    ///
+    /// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+    /// let failures = repo
+    ///     .workflow_runs()
+    ///     .branch("master")
+    ///     .status("failure")
+    ///     .fetch("<my user agent>")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn workflow_runs(&self) -> List<WorkflowRun> {
+        List::new(format!("{}/actions/runs", self.url)).items_key("workflow_runs")
+    }
+
+    /// Lists the jobs of a workflow run.
+    pub fn run_jobs(&self, run_id: u64) -> List<Job> {
+        List::new(format!("{}/actions/runs/{}/jobs", self.url, run_id)).items_key("jobs")
+    }
+}
+
+/// A list of the repositories of a user or organization, from
+/// [`User::repos`] or [`org::repos`].
+///
+/// Unlike other lists, this is its own type, since its filters don't apply
+/// to lists such as [`Repo::forks`].
+///
+/// [`org::repos`]: crate::org::repos
+pub struct RepoList {
+    list: List<Repo>,
+}
+
+impl RepoList {
+    pub(crate) fn new(url: String) -> Self {
+        RepoList {
+            list: List::new(url),
+        }
+    }
+
+    /// Defaults to [`RepoType::Owner`] for a user, and [`RepoType::All`] for
+    /// an organization.
+    pub fn r#type(mut self, r#type: RepoType) -> Self {
+        self.list = self.list.param("type", r#type);
+        self
+    }
+
+    /// Defaults to [`RepoSort::FullName`] for a user, and
+    /// [`RepoSort::Created`] for an organization.
+    pub fn sort(mut self, sort: RepoSort) -> Self {
+        self.list = self.list.param("sort", sort);
+        self
+    }
+
+    /// Defaults to [`Direction::Asc`] when sorting by
+    /// [`RepoSort::FullName`], and [`Direction::Desc`] otherwise.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.list = self.list.param("direction", direction);
+        self
+    }
+
+    /// See [`List::authorization`].
+    pub fn authorization(mut self, token: &str) -> Self {
+        self.list = self.list.authorization(token);
+        self
+    }
+
+    pub(crate) fn authorized(mut self, token: Option<&str>) -> Self {
+        self.list = self.list.authorized(token);
+        self
+    }
+
+    /// See [`List::per_page`].
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.list = self.list.per_page(per_page);
+        self
+    }
+
+    /// See [`List::max_pages`].
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.list = self.list.max_pages(max_pages);
+        self
+    }
+
+    /// See [`List::count`].
+    pub async fn count(&self, user_agent: &str) -> Result<u64> {
+        self.list.count(user_agent).await
+    }
+
+    /// See [`List::fetch`].
+    pub async fn fetch(&self, user_agent: &str) -> Result<Vec<Repo>> {
+        self.list.fetch(user_agent).await
+    }
+}
+
+impl fmt::Display for RepoList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.list.fmt(f)
+    }
 }
 
 // Takes [Github] user and repo IDs to make a link to the API for that repo.
@@ -214,3 +714,63 @@ fn repo_api_url(user: &str, repo: &str) -> String {
     const URL: &str = "https://api.github.com/repos";
     format!("{}/{}/{}", URL, user, repo)
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::user::tests::user;
+
+    /// A repository as [Github] lists it, with some of its fields replaced.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn repo(fields: Value) -> Repo {
+        let mut repo = json!({
+            "id": 1296269,
+            "node_id": "MDEwOlJlcG9zaXRvcnkxMjk2MjY5",
+            "name": "Hello-World",
+            "full_name": "octocat/Hello-World",
+            "private": false,
+            "owner": user("octocat"),
+            "html_url": "https://github.com/octocat/Hello-World",
+            "description": "This your first repo!",
+            "fork": false,
+            "url": "https://api.github.com/repos/octocat/Hello-World",
+            "created_at": "2011-01-26T19:01:12Z",
+            "updated_at": "2011-01-26T19:14:43Z",
+            "pushed_at": "2011-01-26T19:06:43Z",
+            "git_url": "git:github.com/octocat/Hello-World.git",
+            "ssh_url": "git@github.com:octocat/Hello-World.git",
+            "clone_url": "https://github.com/octocat/Hello-World.git",
+            "svn_url": "https://svn.github.com/octocat/Hello-World",
+            "homepage": null,
+            "size": 108,
+            "stargazers_count": 80,
+            "language": null,
+            "forks_count": 9,
+            "archived": false,
+            "disabled": false,
+            "has_projects": true,
+            "has_pages": false,
+            "has_downloads": true,
+            "open_issues": 0,
+            "default_branch": "master",
+            "has_issues": true,
+            "has_wiki": true,
+            "open_issues_count": 0
+        });
+        if let (Value::Object(repo), Value::Object(fields)) = (&mut repo, fields) {
+            repo.extend(fields);
+        }
+        serde_json::from_value(repo).unwrap()
+    }
+
+    #[test]
+    fn listed_repo() {
+        let repo = repo(json!({}));
+
+        assert_eq!(&None, repo.homepage());
+        assert_eq!(None, repo.subscribers_count());
+    }
+}
@@ -0,0 +1,420 @@
+//! Requests to [Github]'s REST API.
+//!
+//! Methods that reach past a single resource, such as
+//! [`Repo::stargazers_with_dates`], return one of these builders so that
+//! filters and an authorization token can be set before the request is sent.
+//!
+//! [`Repo::stargazers_with_dates`]: crate::Repo::stargazers_with_dates
+//! [Github]: https://github.com/
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use reqwest::header::{HeaderMap, ACCEPT, AUTHORIZATION, LINK};
+use reqwest::{Client, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::Result;
+
+const DEFAULT_ACCEPT: &str = "application/vnd.github+json";
+
+/// Largest page size [Github] allows.
+///
+/// [Github]: https://github.com/
+const MAX_PER_PAGE: usize = 100;
+
+/// Sort direction for lists that can be sorted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Direction::*;
+        write!(f, "{}", match self {
+            Asc => "asc",
+            Desc => "desc",
+        })
+    }
+}
+
+/// Parts shared by every request.
+struct Endpoint {
+    url: String,
+    params: Vec<(String, String)>,
+    accept: &'static str,
+    authorization: Option<String>,
+}
+
+impl Endpoint {
+    fn new(url: String) -> Self {
+        Endpoint {
+            url,
+            params: Vec::new(),
+            accept: DEFAULT_ACCEPT,
+            authorization: None,
+        }
+    }
+
+    /// Sets a query parameter, replacing any previous value.
+    fn set(&mut self, key: &str, value: String) {
+        self.params.retain(|(k, _)| k != key);
+        self.params.push((key.to_owned(), value));
+    }
+
+    fn to_url(&self) -> Result<Url> {
+        let mut url = Url::parse(&self.url)?;
+        if !self.params.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.params);
+        }
+        Ok(url)
+    }
+
+    async fn get(&self, client: &Client, url: Url) -> Result<Response> {
+        let request = client.get(url).header(ACCEPT, self.accept);
+        let request = match &self.authorization {
+            Some(t) => request.header(AUTHORIZATION, format!("Bearer {token}", token=t)),
+            None => request,
+        };
+        Ok(request.send().await?.error_for_status()?)
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_url() {
+            Ok(url) => write!(f, "{}", url),
+            Err(_) => write!(f, "{}", self.url),
+        }
+    }
+}
+
+/// A request for a single resource.
+pub struct Request<T> {
+    endpoint: Endpoint,
+    /// Some endpoints wrap the resource in an object, such as
+    /// `{"sbom": {...}}`.
+    key: Option<&'static str>,
+    resource: PhantomData<fn() -> T>,
+}
+
+impl<T> Request<T> {
+    pub(crate) fn new(url: String) -> Self {
+        Request {
+            endpoint: Endpoint::new(url),
+            key: None,
+            resource: PhantomData,
+        }
+    }
+
+    pub(crate) fn accept(mut self, media_type: &'static str) -> Self {
+        self.endpoint.accept = media_type;
+        self
+    }
+
+    pub(crate) fn key(mut self, key: &'static str) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub(crate) fn param<V: ToString>(mut self, key: &str, value: V) -> Self {
+        self.endpoint.set(key, value.to_string());
+        self
+    }
+
+    /// Sets an authorization token for querying the API
+    pub fn authorization(mut self, token: &str) -> Self {
+        self.endpoint.authorization = Some(String::from(token));
+        self
+    }
+
+    /// Sets the authorization token, if any, for helpers that make several
+    /// requests.
+    pub(crate) fn authorized(mut self, token: Option<&str>) -> Self {
+        self.endpoint.authorization = token.map(String::from);
+        self
+    }
+
+    /// Sends the request without interpreting the response body.
+    pub(crate) async fn send(&self, user_agent: &str) -> Result<Response> {
+        let client = client(user_agent)?;
+        self.endpoint.get(&client, self.endpoint.to_url()?).await
+    }
+
+    /// Sends the request, and checks if the resource was found.
+    pub(crate) async fn exists(&self, user_agent: &str) -> Result<bool> {
+        match self.send(user_agent).await {
+            Ok(_) => Ok(true),
+            Err(e) if is_not_found(&e) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Request<T> {
+    /// Sends the request.
+    pub async fn fetch(&self, user_agent: &str) -> Result<T> {
+        let resource: Value = self.send(user_agent).await?.json().await?;
+        Ok(serde_json::from_value(unwrap_key(self.key, resource)?)?)
+    }
+
+    /// Sends the request, and returns `None` if the resource wasn't found.
+    pub(crate) async fn fetch_optional(&self, user_agent: &str) -> Result<Option<T>> {
+        match self.fetch(user_agent).await {
+            Ok(resource) => Ok(Some(resource)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T> fmt::Display for Request<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.endpoint.fmt(f)
+    }
+}
+
+/// A request for a paginated list of resources.
+///
+/// [`fetch`](List::fetch) follows the `Link` header until every page has been
+/// read, or until [`max_pages`](List::max_pages) is reached.
+pub struct List<T> {
+    endpoint: Endpoint,
+    per_page: usize,
+    max_pages: Option<usize>,
+    /// Some endpoints wrap their items in an object, such as
+    /// `{"total_count": 1, "workflow_runs": [...]}`.
+    items_key: Option<&'static str>,
+    items: PhantomData<fn() -> T>,
+}
+
+impl<T> List<T> {
+    pub(crate) fn new(url: String) -> Self {
+        List {
+            endpoint: Endpoint::new(url),
+            per_page: MAX_PER_PAGE,
+            max_pages: None,
+            items_key: None,
+            items: PhantomData,
+        }
+    }
+
+    pub(crate) fn accept(mut self, media_type: &'static str) -> Self {
+        self.endpoint.accept = media_type;
+        self
+    }
+
+    pub(crate) fn items_key(mut self, key: &'static str) -> Self {
+        self.items_key = Some(key);
+        self
+    }
+
+    pub(crate) fn param<V: ToString>(mut self, key: &str, value: V) -> Self {
+        self.endpoint.set(key, value.to_string());
+        self
+    }
+
+    /// Sets an authorization token for querying the API
+    pub fn authorization(mut self, token: &str) -> Self {
+        self.endpoint.authorization = Some(String::from(token));
+        self
+    }
+
+    /// Sets the authorization token, if any, for helpers that make several
+    /// requests.
+    pub(crate) fn authorized(mut self, token: Option<&str>) -> Self {
+        self.endpoint.authorization = token.map(String::from);
+        self
+    }
+
+    /// Defaults to 100, the largest page size allowed.
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.per_page = per_page;
+        self
+    }
+
+    /// Stops after this many pages have been read.
+    ///
+    /// By default, every page is read.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    fn first_page(&self, per_page: usize) -> Result<Url> {
+        let mut url = self.endpoint.to_url()?;
+        url.query_pairs_mut()
+            .append_pair("per_page", &per_page.to_string());
+        Ok(url)
+    }
+
+    /// Counts the items without downloading all of them.
+    ///
+    /// Requests a single item per page and reads the number of the last page
+    /// from the `Link` header.
+    pub async fn count(&self, user_agent: &str) -> Result<u64> {
+        let client = client(user_agent)?;
+        let response = self.endpoint.get(&client, self.first_page(1)?).await?;
+        if let Some(last) = link(response.headers(), "last") {
+            if let Some(page) = page_number(&last) {
+                return Ok(page);
+            }
+        }
+        let page: Value = response.json().await?;
+        let count = match unwrap_key(self.items_key, page)? {
+            Value::Array(items) => items.len(),
+            _ => 0,
+        };
+        Ok(count as u64)
+    }
+}
+
+impl<T: DeserializeOwned> List<T> {
+    /// Sends the request, following every page.
+    pub async fn fetch(&self, user_agent: &str) -> Result<Vec<T>> {
+        self.fetch_until(user_agent, |_| false).await
+    }
+
+    /// Sends the request, following pages until one has an item that
+    /// `done` is true for.
+    ///
+    /// Every item of the pages read is returned, so items past the one that
+    /// stopped the listing still need to be filtered out.
+    pub(crate) async fn fetch_until<F>(&self, user_agent: &str, done: F) -> Result<Vec<T>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let client = client(user_agent)?;
+        let mut items = Vec::new();
+        let mut next = Some(self.first_page(self.per_page)?);
+        let mut pages = 0;
+
+        while let Some(url) = next {
+            if self.max_pages.is_some_and(|max| pages >= max) {
+                break;
+            }
+            let response = self.endpoint.get(&client, url).await?;
+            next = link(response.headers(), "next").and_then(|l| Url::parse(&l).ok());
+            let page: Value = response.json().await?;
+            let page: Vec<T> = serde_json::from_value(unwrap_key(self.items_key, page)?)?;
+            if page.iter().any(&done) {
+                next = None;
+            }
+            items.extend(page);
+            pages += 1;
+        }
+
+        Ok(items)
+    }
+}
+
+impl<T> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.first_page(self.per_page) {
+            Ok(url) => write!(f, "{}", url),
+            Err(_) => self.endpoint.fmt(f),
+        }
+    }
+}
+
+pub(crate) fn client(user_agent: &str) -> Result<Client> {
+    Ok(Client::builder().user_agent(user_agent).build()?)
+}
+
+// Takes the value of `key` out of `value`, if there is a key.
+fn unwrap_key(key: Option<&'static str>, value: Value) -> Result<Value> {
+    match (key, value) {
+        (None, value) => Ok(value),
+        (Some(key), Value::Object(mut object)) => object
+            .remove(key)
+            .ok_or_else(|| format!("missing `{}` in response", key).into()),
+        (Some(key), _) => Err(format!("expected an object with `{}`", key).into()),
+    }
+}
+
+// If the API responded with `404 Not Found`.
+pub(crate) fn is_not_found(error: &crate::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(reqwest::Error::status)
+        == Some(StatusCode::NOT_FOUND)
+}
+
+// Finds the link with relation `rel` in a `Link` header.
+fn link(headers: &HeaderMap, rel: &str) -> Option<String> {
+    let header = headers.get(LINK)?.to_str().ok()?;
+    parse_link(header, rel)
+}
+
+fn parse_link(header: &str, rel: &str) -> Option<String> {
+    let rel = format!("rel=\"{}\"", rel);
+    header.split(',').find_map(|link| {
+        let mut parts = link.split(';').map(str::trim);
+        let url = parts.next()?;
+        if parts.any(|p| p == rel) {
+            Some(url.trim_start_matches('<').trim_end_matches('>').to_owned())
+        } else {
+            None
+        }
+    })
+}
+
+fn page_number(url: &str) -> Option<u64> {
+    Url::parse(url)
+        .ok()?
+        .query_pairs()
+        .find(|(k, _)| k == "page")
+        .and_then(|(_, v)| v.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINK_HEADER: &str = "<https://api.github.com/repositories/724712/commits?per_page=1&page=2>; rel=\"next\", <https://api.github.com/repositories/724712/commits?per_page=1&page=262144>; rel=\"last\"";
+
+    #[test]
+    fn next_link() {
+        assert_eq!(
+            Some("https://api.github.com/repositories/724712/commits?per_page=1&page=2"),
+            parse_link(LINK_HEADER, "next").as_deref(),
+        );
+        assert_eq!(None, parse_link(LINK_HEADER, "prev"));
+    }
+
+    #[test]
+    fn last_page_number() {
+        let last = parse_link(LINK_HEADER, "last").unwrap();
+
+        assert_eq!(Some(262144), page_number(&last));
+    }
+
+    #[test]
+    fn built_list() {
+        const EXPECTED: &str = "https://api.github.com/repos/rust-lang/rust/stargazers?per_page=100";
+        let list: List<Value> = List::new(String::from("https://api.github.com/repos/rust-lang/rust/stargazers"));
+
+        assert_eq!(EXPECTED, list.to_string());
+    }
+
+    #[test]
+    fn replaced_param() {
+        const EXPECTED: &str = "https://api.github.com/repos/rust-lang/rust/forks?sort=oldest&per_page=100";
+        let list: List<Value> = List::new(String::from("https://api.github.com/repos/rust-lang/rust/forks"))
+            .param("sort", "newest")
+            .param("sort", "oldest");
+
+        assert_eq!(EXPECTED, list.to_string());
+    }
+
+    #[test]
+    fn wrapped_items() {
+        let list: List<Value> = List::new(String::new()).items_key("workflow_runs");
+        let page = serde_json::json!({"total_count": 1, "workflow_runs": [{"id": 1}]});
+
+        assert_eq!(serde_json::json!([{"id": 1}]), unwrap_key(list.items_key, page).unwrap());
+    }
+}
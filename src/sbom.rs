@@ -0,0 +1,425 @@
+//! For getting a repository's software bill of materials (SBOM), as
+//! [Github]'s dependency graph reports it in the [SPDX] format.
+//!
+//! [Github]: https://github.com/
+//! [SPDX]: https://spdx.dev/
+
+use std::collections::BTreeMap;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{org, Result};
+
+/// An [SPDX] document describing a repository and its dependencies.
+///
+/// [SPDX]: https://spdx.dev/
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sbom {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    /// Such as `SPDX-2.3`.
+    spdx_version: String,
+    creation_info: CreationInfo,
+    name: String,
+    data_license: String,
+    /// SPDX IDs of the packages the document is about, usually the
+    /// repository itself.
+    #[serde(default)]
+    document_describes: Vec<String>,
+    document_namespace: String,
+    #[serde(default)]
+    packages: Vec<Package>,
+    #[serde(default)]
+    relationships: Vec<Relationship>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreationInfo {
+    created: DateTime<Utc>,
+    /// Such as `Tool: GitHub.com-Dependency-Graph`.
+    creators: Vec<String>,
+}
+
+/// A package in an [`Sbom`].
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Package {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    /// Such as `npm:lodash`.
+    name: String,
+    #[serde(default)]
+    version_info: Option<String>,
+    #[serde(default)]
+    download_location: Option<String>,
+    #[serde(default)]
+    license_concluded: Option<String>,
+    #[serde(default)]
+    license_declared: Option<String>,
+    #[serde(default)]
+    copyright_text: Option<String>,
+    #[serde(default)]
+    supplier: Option<String>,
+    #[serde(default)]
+    external_refs: Vec<ExternalRef>,
+}
+
+/// A reference to a package outside the document, such as its
+/// [package URL].
+///
+/// [package URL]: https://github.com/package-url/purl-spec
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalRef {
+    /// Such as `PACKAGE-MANAGER`.
+    reference_category: String,
+    /// Such as `pkg:npm/lodash@4.17.21`.
+    reference_locator: String,
+    /// Such as `purl`.
+    reference_type: String,
+}
+
+/// How two elements of an [`Sbom`] are related.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Relationship {
+    /// Such as `DEPENDS_ON` or `DESCRIBES`.
+    relationship_type: String,
+    spdx_element_id: String,
+    related_spdx_element: String,
+}
+
+impl Sbom {
+    pub fn spdx_id(&self) -> &str {
+        &self.spdx_id
+    }
+
+    /// Such as `SPDX-2.3`.
+    pub fn spdx_version(&self) -> &str {
+        &self.spdx_version
+    }
+
+    pub fn creation_info(&self) -> &CreationInfo {
+        &self.creation_info
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn data_license(&self) -> &str {
+        &self.data_license
+    }
+
+    /// SPDX IDs of the packages the document is about, usually the
+    /// repository itself.
+    pub fn document_describes(&self) -> &[String] {
+        &self.document_describes
+    }
+
+    pub fn document_namespace(&self) -> &str {
+        &self.document_namespace
+    }
+
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    pub fn relationships(&self) -> &[Relationship] {
+        &self.relationships
+    }
+
+    pub fn package(&self, spdx_id: &str) -> Option<&Package> {
+        self.packages.iter().find(|p| p.spdx_id == spdx_id)
+    }
+
+    /// Every package except the ones the document describes.
+    pub fn dependencies(&self) -> impl Iterator<Item = &Package> {
+        self.packages
+            .iter()
+            .filter(move |p| !self.document_describes.contains(&p.spdx_id))
+    }
+
+    /// Packages that `spdx_id` has a `DEPENDS_ON` relationship with.
+    pub fn dependencies_of(&self, spdx_id: &str) -> Vec<&Package> {
+        self.relationships
+            .iter()
+            .filter(|r| r.relationship_type == "DEPENDS_ON" && r.spdx_element_id == spdx_id)
+            .filter_map(|r| self.package(&r.related_spdx_element))
+            .collect()
+    }
+
+    /// Number of dependencies per ecosystem, such as `npm` or `cargo`.
+    ///
+    /// Dependencies without a package URL are counted under `unknown`.
+    pub fn counts_per_ecosystem(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for package in self.dependencies() {
+            let ecosystem = package.ecosystem().unwrap_or("unknown");
+            *counts.entry(ecosystem.to_owned()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// If any dependency is the package `name` from `ecosystem`, such as
+    /// `serde` from `cargo`.
+    pub fn depends_on(&self, ecosystem: &str, name: &str) -> bool {
+        self.dependencies()
+            .any(|p| p.ecosystem() == Some(ecosystem) && p.package_name().as_deref() == Some(name))
+    }
+}
+
+impl CreationInfo {
+    pub fn created(&self) -> &DateTime<Utc> {
+        &self.created
+    }
+
+    /// Such as `Tool: GitHub.com-Dependency-Graph`.
+    pub fn creators(&self) -> &[String] {
+        &self.creators
+    }
+}
+
+impl Package {
+    pub fn spdx_id(&self) -> &str {
+        &self.spdx_id
+    }
+
+    /// Such as `npm:lodash`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version_info(&self) -> &Option<String> {
+        &self.version_info
+    }
+
+    pub fn download_location(&self) -> &Option<String> {
+        &self.download_location
+    }
+
+    pub fn license_concluded(&self) -> &Option<String> {
+        &self.license_concluded
+    }
+
+    pub fn license_declared(&self) -> &Option<String> {
+        &self.license_declared
+    }
+
+    pub fn copyright_text(&self) -> &Option<String> {
+        &self.copyright_text
+    }
+
+    pub fn supplier(&self) -> &Option<String> {
+        &self.supplier
+    }
+
+    pub fn external_refs(&self) -> &[ExternalRef] {
+        &self.external_refs
+    }
+
+    /// Such as `pkg:npm/lodash@4.17.21`.
+    pub fn purl(&self) -> Option<&str> {
+        self.external_refs
+            .iter()
+            .find(|r| r.reference_type == "purl")
+            .map(|r| r.reference_locator.as_str())
+    }
+
+    /// Such as `npm` or `cargo`, from the package URL.
+    pub fn ecosystem(&self) -> Option<&str> {
+        let purl = self.purl()?.strip_prefix("pkg:")?;
+        purl.split('/').next().filter(|e| !e.is_empty())
+    }
+
+    /// Name of the package in its ecosystem, such as `@babel/core`, from the
+    /// package URL.
+    pub fn package_name(&self) -> Option<String> {
+        let purl = self.purl()?.strip_prefix("pkg:")?;
+        let path = purl.split(['@', '?', '#']).next()?;
+        // A scoped npm package starts with an encoded `@`, so `@` can only
+        // separate the version.
+        let (_, name) = path.split_once('/')?;
+        Some(percent_decode(name))
+    }
+}
+
+impl ExternalRef {
+    /// Such as `PACKAGE-MANAGER`.
+    pub fn reference_category(&self) -> &str {
+        &self.reference_category
+    }
+
+    /// Such as `pkg:npm/lodash@4.17.21`.
+    pub fn reference_locator(&self) -> &str {
+        &self.reference_locator
+    }
+
+    /// Such as `purl`.
+    pub fn reference_type(&self) -> &str {
+        &self.reference_type
+    }
+}
+
+impl Relationship {
+    /// Such as `DEPENDS_ON` or `DESCRIBES`.
+    pub fn relationship_type(&self) -> &str {
+        &self.relationship_type
+    }
+
+    pub fn spdx_element_id(&self) -> &str {
+        &self.spdx_element_id
+    }
+
+    pub fn related_spdx_element(&self) -> &str {
+        &self.related_spdx_element
+    }
+}
+
+/// Finds the repositories of `org` that depend on the package `name` from
+/// `ecosystem`, such as `serde` from `cargo`.
+///
+/// Archived repositories, and those without a dependency graph, are
+/// skipped. Makes one request per repository.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::sbom;
+///
+/// let repos = sbom::dependents("rust-lang", "cargo", "serde", "<my user agent>", None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn dependents(
+    org: &str,
+    ecosystem: &str,
+    name: &str,
+    user_agent: &str,
+    authorization: Option<&str>,
+) -> Result<Vec<String>> {
+    let repos = org::repos(org)
+        .authorized(authorization)
+        .fetch(user_agent)
+        .await?;
+    let mut dependents = Vec::new();
+    for repo in repos.iter().filter(|r| !r.archived()) {
+        let sbom = repo
+            .sbom()
+            .authorized(authorization)
+            .fetch_optional(user_agent)
+            .await?;
+        if sbom.is_some_and(|s| s.depends_on(ecosystem, name)) {
+            dependents.push(repo.full_name().to_owned());
+        }
+    }
+    Ok(dependents)
+}
+
+// Decodes `%XX` escapes, leaving anything invalid as it is.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn package(spdx_id: &str, name: &str, purl: Option<&str>) -> Value {
+        json!({
+            "SPDXID": spdx_id,
+            "name": name,
+            "versionInfo": "1.0.0",
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "licenseConcluded": "MIT",
+            "supplier": "NOASSERTION",
+            "externalRefs": purl.map(|p| vec![json!({
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceLocator": p,
+                "referenceType": "purl"
+            })]).unwrap_or_default()
+        })
+    }
+
+    fn sbom() -> Sbom {
+        serde_json::from_value(json!({
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "spdxVersion": "SPDX-2.3",
+            "creationInfo": {
+                "created": "2021-09-01T00:00:00Z",
+                "creators": ["Tool: GitHub.com-Dependency-Graph"]
+            },
+            "name": "com.github.octocat/Hello-World",
+            "dataLicense": "CC0-1.0",
+            "documentDescribes": ["SPDXRef-com.github.octocat-Hello-World"],
+            "documentNamespace": "https://github.com/octocat/Hello-World/dependency_graph/sbom-abcdef123456",
+            "packages": [
+                package("SPDXRef-com.github.octocat-Hello-World", "com.github.octocat/Hello-World", Some("pkg:github/octocat/Hello-World@master")),
+                package("SPDXRef-npm-babel-core-7.0.0", "npm:@babel/core", Some("pkg:npm/%40babel/core@7.0.0")),
+                package("SPDXRef-npm-lodash-4.17.21", "npm:lodash", Some("pkg:npm/lodash@4.17.21")),
+                package("SPDXRef-cargo-serde-1.0.0", "cargo:serde", Some("pkg:cargo/serde@1.0.0")),
+                package("SPDXRef-unknown", "mystery", None)
+            ],
+            "relationships": [
+                {
+                    "relationshipType": "DEPENDS_ON",
+                    "spdxElementId": "SPDXRef-com.github.octocat-Hello-World",
+                    "relatedSpdxElement": "SPDXRef-npm-lodash-4.17.21"
+                },
+                {
+                    "relationshipType": "DESCRIBES",
+                    "spdxElementId": "SPDXRef-DOCUMENT",
+                    "relatedSpdxElement": "SPDXRef-com.github.octocat-Hello-World"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn ecosystem_counts() {
+        let counts = sbom().counts_per_ecosystem();
+
+        assert_eq!(Some(&2), counts.get("npm"));
+        assert_eq!(Some(&1), counts.get("cargo"));
+        assert_eq!(Some(&1), counts.get("unknown"));
+        assert_eq!(None, counts.get("github"));
+    }
+
+    #[test]
+    fn package_dependents() {
+        let sbom = sbom();
+        let direct: Vec<_> = sbom
+            .dependencies_of("SPDXRef-com.github.octocat-Hello-World")
+            .iter()
+            .map(|p| p.name())
+            .collect();
+
+        assert!(sbom.depends_on("npm", "@babel/core"));
+        assert!(sbom.depends_on("cargo", "serde"));
+        assert!(!sbom.depends_on("npm", "serde"));
+        assert_eq!(vec!["npm:lodash"], direct);
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::Result;
@@ -60,7 +60,7 @@ impl fmt::Display for SearchArea {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct SearchResults {
     total_count: u64,
     items: Vec<Value>,
@@ -110,14 +110,14 @@ impl Search {
 
     /// Moves one page forward.
     pub fn next_page(&mut self) {
-        if self.page < std::usize::MAX {
+        if self.page < usize::MAX {
             self.page += 1;
         }
     }
 
     /// Moves one page backward.
     pub fn prev_page(&mut self) {
-        if self.page > std::usize::MIN {
+        if self.page > usize::MIN {
             self.page -= 1;
         }
     }
@@ -127,7 +127,7 @@ impl Search {
         let request = reqwest::Client::builder()
              .user_agent(user_agent)
              .build()?
-             .get(&self.to_string());
+             .get(self.to_string());
         let request = match &self.authorization {
             Some(t) => request.header("Authorization", format!("Bearer {token}", token=t)),
             None => request,
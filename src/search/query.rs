@@ -128,6 +128,14 @@ impl Query {
         self
     }
 
+    /// *Adds* a `state` statement to the query.
+    ///
+    /// Results in `state:statement`.
+    pub fn state(mut self, statement: &str) -> Self {
+        self.state.push(String::from(statement));
+        self
+    }
+
     /// *Adds* a `no` statement to the query.
     ///
     /// Results in `no:statement`.
@@ -0,0 +1,490 @@
+//! For saving the key numbers of a repository, and reporting what changed
+//! between two of them.
+
+use std::fmt;
+
+use chrono::prelude::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Repo, Result};
+
+/// The counters and settings of a [`Repo`] at the time it was fetched.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::snapshot::RepoSnapshot;
+///
+/// let snapshot = RepoSnapshot::fetch("rust-lang", "rust", "<my user agent>").await?;
+/// let json = serde_json::to_string(&snapshot)?;
+///
+/// let saved: RepoSnapshot = serde_json::from_str(&json)?;
+/// assert_eq!(snapshot, saved);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RepoSnapshot {
+    id: u64,
+    full_name: String,
+    fetched_at: DateTime<Utc>,
+    description: Option<String>,
+    homepage: Option<String>,
+    language: Option<String>,
+    default_branch: String,
+    private: bool,
+    fork: bool,
+    archived: bool,
+    disabled: bool,
+    has_issues: bool,
+    has_projects: bool,
+    has_wiki: bool,
+    has_pages: bool,
+    has_downloads: bool,
+    stargazers: u64,
+    forks: u64,
+    /// `None` if the snapshot was taken of a listed repository.
+    watchers: Option<u64>,
+    open_issues: u64,
+    /// In *kilo*bytes.
+    size: u64,
+    pushed_at: DateTime<Utc>,
+}
+
+impl RepoSnapshot {
+    pub fn new(repo: &Repo, fetched_at: DateTime<Utc>) -> Self {
+        RepoSnapshot {
+            id: repo.id(),
+            full_name: String::from(repo.full_name()),
+            fetched_at,
+            description: repo.description().clone(),
+            homepage: repo.homepage().clone(),
+            language: repo.language().clone(),
+            default_branch: String::from(repo.default_branch()),
+            private: repo.private(),
+            fork: repo.fork(),
+            archived: repo.archived(),
+            disabled: repo.disabled(),
+            has_issues: repo.has_issues(),
+            has_projects: repo.has_projects(),
+            has_wiki: repo.has_wiki(),
+            has_pages: repo.has_pages(),
+            has_downloads: repo.has_downloads(),
+            stargazers: repo.stargazers_count(),
+            forks: repo.forks_count(),
+            watchers: repo.subscribers_count(),
+            open_issues: repo.open_issues_count(),
+            size: repo.size(),
+            pushed_at: *repo.pushed_at(),
+        }
+    }
+
+    /// Gets the repository `user/repo` and takes a snapshot of it.
+    pub async fn fetch(user: &str, repo: &str, user_agent: &str) -> Result<Self> {
+        let repo = Repo::new(user, repo, user_agent).await?;
+        Ok(RepoSnapshot::new(&repo, Utc::now()))
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    pub fn fetched_at(&self) -> &DateTime<Utc> {
+        &self.fetched_at
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn homepage(&self) -> &Option<String> {
+        &self.homepage
+    }
+
+    pub fn language(&self) -> &Option<String> {
+        &self.language
+    }
+
+    pub fn default_branch(&self) -> &str {
+        &self.default_branch
+    }
+
+    pub fn private(&self) -> bool {
+        self.private
+    }
+
+    pub fn fork(&self) -> bool {
+        self.fork
+    }
+
+    pub fn archived(&self) -> bool {
+        self.archived
+    }
+
+    pub fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    pub fn has_issues(&self) -> bool {
+        self.has_issues
+    }
+
+    pub fn has_projects(&self) -> bool {
+        self.has_projects
+    }
+
+    pub fn has_wiki(&self) -> bool {
+        self.has_wiki
+    }
+
+    pub fn has_pages(&self) -> bool {
+        self.has_pages
+    }
+
+    pub fn has_downloads(&self) -> bool {
+        self.has_downloads
+    }
+
+    pub fn stargazers(&self) -> u64 {
+        self.stargazers
+    }
+
+    pub fn forks(&self) -> u64 {
+        self.forks
+    }
+
+    /// Only counted when a single repository is fetched, not when
+    /// repositories are listed.
+    pub fn watchers(&self) -> Option<u64> {
+        self.watchers
+    }
+
+    /// Issues + PRs
+    pub fn open_issues(&self) -> u64 {
+        self.open_issues
+    }
+
+    /// In *kilo*bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn pushed_at(&self) -> &DateTime<Utc> {
+        &self.pushed_at
+    }
+
+    /// What changed from this snapshot to `newer`.
+    ///
+    /// Counters that either snapshot doesn't know, such as the watchers of a
+    /// listed repository, are left out.
+    ///
+    /// Fails if `newer` is a snapshot of a different repository. Renamed and
+    /// transferred repositories keep their ID, so those can be compared.
+    pub fn diff(&self, newer: &RepoSnapshot) -> Result<SnapshotDiff> {
+        if self.id != newer.id {
+            return Err(format!(
+                "`{}` and `{}` are different repositories",
+                self.full_name, newer.full_name,
+            )
+            .into());
+        }
+
+        let mut changes = Vec::new();
+        if self.full_name != newer.full_name {
+            changes.push(Change::Renamed {
+                old: self.full_name.clone(),
+                new: newer.full_name.clone(),
+            });
+        }
+
+        let text = [
+            ("description", &self.description, &newer.description),
+            ("homepage", &self.homepage, &newer.homepage),
+            ("language", &self.language, &newer.language),
+        ];
+        for (field, old, new) in text.iter() {
+            if old != new {
+                changes.push(Change::Field {
+                    field: String::from(*field),
+                    old: (*old).clone(),
+                    new: (*new).clone(),
+                });
+            }
+        }
+        if self.default_branch != newer.default_branch {
+            changes.push(Change::Field {
+                field: String::from("default_branch"),
+                old: Some(self.default_branch.clone()),
+                new: Some(newer.default_branch.clone()),
+            });
+        }
+        if self.pushed_at != newer.pushed_at {
+            changes.push(Change::Field {
+                field: String::from("pushed_at"),
+                old: Some(self.pushed_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                new: Some(newer.pushed_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            });
+        }
+
+        let toggles = [
+            ("private", self.private, newer.private),
+            ("fork", self.fork, newer.fork),
+            ("archived", self.archived, newer.archived),
+            ("disabled", self.disabled, newer.disabled),
+            ("has_issues", self.has_issues, newer.has_issues),
+            ("has_projects", self.has_projects, newer.has_projects),
+            ("has_wiki", self.has_wiki, newer.has_wiki),
+            ("has_pages", self.has_pages, newer.has_pages),
+            ("has_downloads", self.has_downloads, newer.has_downloads),
+        ];
+        for (field, old, new) in toggles.iter() {
+            if old != new {
+                changes.push(Change::Toggled {
+                    field: String::from(*field),
+                    new: *new,
+                });
+            }
+        }
+
+        let counters = [
+            ("stargazers", Some(self.stargazers), Some(newer.stargazers)),
+            ("forks", Some(self.forks), Some(newer.forks)),
+            ("watchers", self.watchers, newer.watchers),
+            ("open_issues", Some(self.open_issues), Some(newer.open_issues)),
+            ("size", Some(self.size), Some(newer.size)),
+        ];
+        for (field, old, new) in counters.iter() {
+            if let (Some(old), Some(new)) = (old, new) {
+                if old != new {
+                    changes.push(Change::Counter {
+                        field: String::from(*field),
+                        old: *old,
+                        new: *new,
+                    });
+                }
+            }
+        }
+
+        Ok(SnapshotDiff {
+            full_name: newer.full_name.clone(),
+            from: self.fetched_at,
+            to: newer.fetched_at,
+            changes,
+        })
+    }
+}
+
+impl From<&Repo> for RepoSnapshot {
+    /// Takes a snapshot as of now.
+    fn from(repo: &Repo) -> Self {
+        RepoSnapshot::new(repo, Utc::now())
+    }
+}
+
+/// What changed between two snapshots of a repository, from
+/// [`RepoSnapshot::diff`].
+///
+/// Renders as one line per change.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::snapshot::RepoSnapshot;
+///
+/// let saved = std::fs::read_to_string("rust.json")?;
+/// let last_week: RepoSnapshot = serde_json::from_str(&saved)?;
+/// let now = RepoSnapshot::fetch("rust-lang", "rust", "<my user agent>").await?;
+///
+/// print!("{}", last_week.diff(&now)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SnapshotDiff {
+    /// As of the newer snapshot.
+    full_name: String,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    changes: Vec<Change>,
+}
+
+/// A single difference between two snapshots.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// The repository's full name changed, because it was renamed or
+    /// transferred.
+    Renamed { old: String, new: String },
+    /// A text field changed, such as `default_branch`.
+    Field {
+        field: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    /// A setting such as `archived` was turned on or off.
+    Toggled { field: String, new: bool },
+    /// A counter such as `stargazers` went up or down.
+    Counter { field: String, old: u64, new: u64 },
+}
+
+impl SnapshotDiff {
+    /// Full name of the repository as of the newer snapshot.
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    /// When the older snapshot was fetched.
+    pub fn from(&self) -> &DateTime<Utc> {
+        &self.from
+    }
+
+    /// When the newer snapshot was fetched.
+    pub fn to(&self) -> &DateTime<Utc> {
+        &self.to
+    }
+
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// How much the counter `field` went up or down. `0` if it didn't change.
+    pub fn delta(&self, field: &str) -> i64 {
+        self.changes
+            .iter()
+            .find_map(|c| match c {
+                Change::Counter { field: f, .. } if f == field => c.delta(),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl Change {
+    /// How much a [`Change::Counter`] went up or down.
+    pub fn delta(&self) -> Option<i64> {
+        match self {
+            Change::Counter { old, new, .. } => Some(*new as i64 - *old as i64),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} from {} to {}",
+            self.full_name,
+            self.from.format("%Y-%m-%d"),
+            self.to.format("%Y-%m-%d"),
+        )?;
+        if self.changes.is_empty() {
+            return writeln!(f, "  no changes");
+        }
+        for change in &self.changes {
+            writeln!(f, "  {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Change::*;
+        let text = |value: &Option<String>| value.clone().unwrap_or_else(|| String::from("none"));
+        match self {
+            Renamed { old, new } => write!(f, "renamed from {} to {}", old, new),
+            Field { field, old, new } => write!(f, "{} changed from {} to {}", field, text(old), text(new)),
+            Toggled { field, new } => write!(f, "{} turned {}", field, if *new { "on" } else { "off" }),
+            Counter { field, .. } => write!(f, "{:+} {}", self.delta().unwrap_or(0), field),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::repository::tests::repo;
+
+    #[test]
+    fn json_round_trip() {
+        let repo = repo(json!({"stargazers_count": 80, "subscribers_count": 42}));
+        let snapshot = RepoSnapshot::new(&repo, "2020-01-01T00:00:00Z".parse().unwrap());
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let saved: RepoSnapshot = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(snapshot, saved);
+        assert_eq!(80, saved.stargazers());
+
+        let json = serde_json::to_value(&repo).unwrap();
+        let repo: Repo = serde_json::from_value(json).unwrap();
+        assert_eq!(snapshot, RepoSnapshot::new(&repo, *snapshot.fetched_at()));
+    }
+
+    #[test]
+    fn snapshot_diff() {
+        let old = RepoSnapshot::new(&repo(json!({})), "2020-01-01T00:00:00Z".parse().unwrap());
+        let new = RepoSnapshot::new(
+            &repo(json!({
+                "full_name": "octocat/Hello",
+                "default_branch": "main",
+                "archived": true,
+                "stargazers_count": 92,
+                "forks_count": 8
+            })),
+            "2020-01-08T00:00:00Z".parse().unwrap(),
+        );
+        let diff = old.diff(&new).unwrap();
+
+        assert_eq!(12, diff.delta("stargazers"));
+        assert_eq!(-1, diff.delta("forks"));
+        assert_eq!(0, diff.delta("watchers"));
+        assert_eq!(
+            "octocat/Hello from 2020-01-01 to 2020-01-08\n\
+             \x20 renamed from octocat/Hello-World to octocat/Hello\n\
+             \x20 default_branch changed from master to main\n\
+             \x20 archived turned on\n\
+             \x20 +12 stargazers\n\
+             \x20 -1 forks\n",
+            diff.to_string(),
+        );
+        assert!(old.diff(&old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn unknown_watchers() {
+        let listed = RepoSnapshot::new(&repo(json!({})), "2020-01-01T00:00:00Z".parse().unwrap());
+        let fetched = RepoSnapshot::new(
+            &repo(json!({"subscribers_count": 42})),
+            "2020-01-08T00:00:00Z".parse().unwrap(),
+        );
+
+        assert_eq!(None, listed.watchers());
+        assert!(listed.diff(&fetched).unwrap().is_empty());
+        assert!(fetched.diff(&listed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn different_repos() {
+        let fetched_at = "2020-01-01T00:00:00Z".parse().unwrap();
+        let hello = RepoSnapshot::new(&repo(json!({})), fetched_at);
+        let spoon = RepoSnapshot::new(
+            &repo(json!({"id": 1300192, "full_name": "octocat/Spoon-Knife"})),
+            fetched_at,
+        );
+
+        assert!(hello.diff(&spoon).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! For getting when a repository was starred.
+
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::User;
+
+/// Media type that makes [Github] include `starred_at` with each stargazer.
+///
+/// [Github]: https://github.com/
+pub(crate) const MEDIA_TYPE: &str = "application/vnd.github.star+json";
+
+/// A user who starred a repository, and when they starred it.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Stargazer {
+    starred_at: DateTime<Utc>,
+    user: User,
+}
+
+impl Stargazer {
+    pub fn starred_at(&self) -> &DateTime<Utc> {
+        &self.starred_at
+    }
+
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+}
+
+/// Cumulative star count over time.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::Repo;
+/// use github_stats::stargazer::StarHistory;
+///
+/// let repo = Repo::new("rust-lang", "rust", "<my user agent>").await?;
+/// let stargazers = repo
+///     .stargazers_with_dates()
+///     .fetch("<my user agent>")
+///     .await?;
+///
+/// for (date, stars) in StarHistory::new(&stargazers).points() {
+///     println!("{}: {}", date, stars);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StarHistory {
+    points: Vec<(DateTime<Utc>, u64)>,
+}
+
+impl StarHistory {
+    pub fn new(stargazers: &[Stargazer]) -> Self {
+        let mut dates: Vec<_> = stargazers.iter().map(|s| s.starred_at).collect();
+        dates.sort();
+        let points = dates
+            .into_iter()
+            .zip(1..)
+            .collect();
+        StarHistory { points }
+    }
+
+    /// Each star's timestamp paired with the total number of stars at that
+    /// time, oldest first.
+    pub fn points(&self) -> &[(DateTime<Utc>, u64)] {
+        &self.points
+    }
+
+    /// Number of stars the repository had at `date`.
+    pub fn stars_at(&self, date: &DateTime<Utc>) -> u64 {
+        let index = self.points.partition_point(|(d, _)| d <= date);
+        index as u64
+    }
+
+    /// Number of stars gained after `start` and up to `end`.
+    pub fn gained_between(&self, start: &DateTime<Utc>, end: &DateTime<Utc>) -> u64 {
+        self.stars_at(end).saturating_sub(self.stars_at(start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::user::tests::user;
+
+    fn history() -> StarHistory {
+        let stargazers: Vec<Stargazer> = serde_json::from_value(json!([
+            {"starred_at": "2020-01-03T00:00:00Z", "user": user("octocat")},
+            {"starred_at": "2020-01-01T00:00:00Z", "user": user("monalisa")},
+            {"starred_at": "2020-01-02T00:00:00Z", "user": user("hubot")},
+        ]))
+        .unwrap();
+        StarHistory::new(&stargazers)
+    }
+
+    #[test]
+    fn cumulative_points() {
+        let history = history();
+        let counts: Vec<u64> = history.points().iter().map(|(_, n)| *n).collect();
+
+        assert_eq!(vec![1, 2, 3], counts);
+        assert!(history.points().windows(2).all(|w| w[0].0 <= w[1].0));
+    }
+
+    #[test]
+    fn stars_gained() {
+        let history = history();
+        let start = "2020-01-01T12:00:00Z".parse().unwrap();
+        let end = "2020-01-03T00:00:00Z".parse().unwrap();
+
+        assert_eq!(1, history.stars_at(&start));
+        assert_eq!(2, history.gained_between(&start, &end));
+    }
+}
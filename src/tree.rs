@@ -0,0 +1,268 @@
+//! For getting every path in a repository at once, and stats about them.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::request::Request;
+use crate::{Repo, Result};
+
+/// A git tree, listing the paths in a commit.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Tree {
+    sha: String,
+    url: String,
+    tree: Vec<TreeEntry>,
+    /// If [Github] left entries out because the tree is too large.
+    ///
+    /// [Github]: https://github.com/
+    truncated: bool,
+}
+
+/// A file, directory or submodule in a [`Tree`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TreeEntry {
+    path: String,
+    /// Such as `100644` for a file or `040000` for a directory.
+    mode: String,
+    r#type: TreeEntryType,
+    sha: String,
+    /// In bytes. Only set for blobs.
+    #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeEntryType {
+    /// A file.
+    Blob,
+    /// A directory.
+    Tree,
+    /// A submodule.
+    Commit,
+}
+
+impl Tree {
+    /// Gets every path in `r#ref`, which can be a branch, tag, commit SHA or
+    /// tree SHA.
+    ///
+    /// If [Github] truncates the tree, it's walked one directory at a time
+    /// instead, which makes a request per directory that couldn't be listed
+    /// at once. The result is only [truncated](Tree::truncated) if a single
+    /// directory is too large to list.
+    ///
+    /// [Github]: https://github.com/
+    pub async fn fetch_complete(
+        repo: &Repo,
+        r#ref: &str,
+        user_agent: &str,
+        authorization: Option<&str>,
+    ) -> Result<Self> {
+        let tree = repo
+            .tree(r#ref)
+            .authorized(authorization)
+            .fetch(user_agent)
+            .await?;
+        if !tree.truncated {
+            return Ok(tree);
+        }
+
+        let mut complete = Tree {
+            sha: tree.sha.clone(),
+            url: tree.url,
+            tree: Vec::new(),
+            truncated: false,
+        };
+        // Directories left to list, with the path to prefix their entries
+        // with. The root is already known to be too large.
+        let mut pending = vec![(String::new(), tree.sha, false)];
+        while let Some((prefix, sha, recursive)) = pending.pop() {
+            if recursive {
+                let subtree = repo
+                    .tree(&sha)
+                    .authorized(authorization)
+                    .fetch(user_agent)
+                    .await?;
+                if !subtree.truncated {
+                    complete.extend(&prefix, subtree.tree);
+                    continue;
+                }
+            }
+            let level = Request::<Tree>::new(format!("{}/git/trees/{}", repo.url(), sha))
+                .authorized(authorization)
+                .fetch(user_agent)
+                .await?;
+            complete.truncated |= level.truncated;
+            for entry in &level.tree {
+                if entry.r#type == TreeEntryType::Tree {
+                    pending.push((format!("{}{}/", prefix, entry.path), entry.sha.clone(), true));
+                }
+            }
+            complete.extend(&prefix, level.tree);
+        }
+        complete.tree.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(complete)
+    }
+
+    fn extend(&mut self, prefix: &str, entries: Vec<TreeEntry>) {
+        self.tree.extend(entries.into_iter().map(|mut entry| {
+            entry.path.insert_str(0, prefix);
+            entry
+        }));
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn entries(&self) -> &[TreeEntry] {
+        &self.tree
+    }
+
+    /// If [Github] left entries out because the tree is too large.
+    ///
+    /// [Github]: https://github.com/
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Every blob, which are the files of the tree.
+    pub fn files(&self) -> impl Iterator<Item = &TreeEntry> {
+        self.tree.iter().filter(|e| e.r#type == TreeEntryType::Blob)
+    }
+
+    /// Number of files per extension, such as `rs`.
+    ///
+    /// Files without an extension, including dotfiles such as `.gitignore`,
+    /// are counted under `""`.
+    pub fn files_per_extension(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for file in self.files() {
+            *counts.entry(file.extension().unwrap_or("")).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Total size of every file, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.files().filter_map(|f| f.size).sum()
+    }
+
+    /// Up to `n` of the most deeply nested files, deepest first.
+    pub fn deepest_files(&self, n: usize) -> Vec<&TreeEntry> {
+        let mut files: Vec<_> = self.files().collect();
+        files.sort_by(|a, b| b.depth().cmp(&a.depth()).then_with(|| a.path.cmp(&b.path)));
+        files.truncate(n);
+        files
+    }
+
+    /// Up to `n` of the largest files, largest first.
+    pub fn largest_files(&self, n: usize) -> Vec<&TreeEntry> {
+        let mut files: Vec<_> = self.files().collect();
+        files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        files.truncate(n);
+        files
+    }
+}
+
+impl TreeEntry {
+    /// Relative to the root of the repository.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Such as `100644` for a file or `040000` for a directory.
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    pub fn r#type(&self) -> TreeEntryType {
+        self.r#type
+    }
+
+    pub fn sha(&self) -> &str {
+        &self.sha
+    }
+
+    /// In bytes. Only set for blobs.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    pub fn url(&self) -> &Option<String> {
+        &self.url
+    }
+
+    /// Such as `rs` for `src/lib.rs`. `None` for dotfiles such as
+    /// `.gitignore`.
+    pub fn extension(&self) -> Option<&str> {
+        let name = self.path.rsplit('/').next()?;
+        match name.rfind('.') {
+            Some(0) | None => None,
+            Some(dot) => Some(&name[dot + 1..]),
+        }
+    }
+
+    /// Number of directories the entry is nested in.
+    pub fn depth(&self) -> usize {
+        self.path.matches('/').count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn blob(path: &str, size: u64) -> Value {
+        json!({
+            "path": path,
+            "mode": "100644",
+            "type": "blob",
+            "sha": "7c258a9869f33c1e1e1f74fbb32f07c86cb5a75b",
+            "size": size,
+            "url": "https://api.github.com/repos/octocat/Hello-World/git/blobs/7c258a9869f33c1e1e1f74fbb32f07c86cb5a75b"
+        })
+    }
+
+    #[test]
+    fn tree_stats() {
+        let tree: Tree = serde_json::from_value(json!({
+            "sha": "9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "url": "https://api.github.com/repos/octocat/Hello-World/trees/9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "tree": [
+                blob(".gitignore", 10),
+                blob("README.md", 100),
+                {
+                    "path": "src",
+                    "mode": "040000",
+                    "type": "tree",
+                    "sha": "f484d249c660418515fb01c2b9662073663c242e",
+                    "url": "https://api.github.com/repos/octocat/Hello-World/git/trees/f484d249c660418515fb01c2b9662073663c242e"
+                },
+                blob("src/lib.rs", 2000),
+                blob("src/metrics/pulls.rs", 500),
+                blob("Makefile", 50)
+            ],
+            "truncated": false
+        }))
+        .unwrap();
+        let extensions = tree.files_per_extension();
+        let deepest: Vec<_> = tree.deepest_files(2).iter().map(|e| e.path()).collect();
+        let largest: Vec<_> = tree.largest_files(1).iter().map(|e| e.path()).collect();
+
+        assert_eq!(Some(&2), extensions.get(""));
+        assert_eq!(Some(&2), extensions.get("rs"));
+        assert_eq!(2660, tree.total_size());
+        assert_eq!(vec!["src/metrics/pulls.rs", "src/lib.rs"], deepest);
+        assert_eq!(vec!["src/lib.rs"], largest);
+    }
+}
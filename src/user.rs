@@ -1,13 +1,25 @@
 //! For getting user information.
 
-use serde::Deserialize;
+use chrono::prelude::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
-use crate::Result;
+use crate::request::Request;
+use crate::{RepoList, Result};
+
+const URL: &str = "https://api.github.com/users";
+const CURRENT_USER_URL: &str = "https://api.github.com/user";
+
+/// Lists the OAuth scopes of a token.
+const OAUTH_SCOPES: &str = "x-oauth-scopes";
 
 /// Represents that stats of a [Github] user.
 ///
+/// This is the user as it's included in other resources, such as
+/// [`Repo::owner`](crate::Repo::owner). See [`UserProfile`] for the profile
+/// and its stats.
+///
 /// [Github]: https://github.com/
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct User {
     login: String,
     id: u64,
@@ -40,7 +52,6 @@ impl User {
     /// let user = User::new("rust-lang", "<my user agent>");
     /// ```
     pub async fn new(user: &str, user_agent: &str) -> Result<Self> {
-        const URL: &str = "https://api.github.com/users";
         let url = format!("{}/{}", URL, user);
         let user: User = reqwest::Client::builder()
             .user_agent(user_agent)
@@ -109,4 +120,436 @@ impl User {
     pub fn site_admin(&self) -> bool {
         self.site_admin
     }
+
+    /// Gets the user's full profile.
+    pub fn profile(&self) -> Request<UserProfile> {
+        Request::new(self.url.clone())
+    }
+
+    /// Lists the user's repositories.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::{RepoSort, RepoType, User};
+    /// use github_stats::request::Direction;
+    ///
+    /// let user = User::new("octocat", "<my user agent>").await?;
+    /// let repos = user
+    ///     .repos()
+    ///     .r#type(RepoType::All)
+    ///     .sort(RepoSort::Pushed)
+    ///     .direction(Direction::Desc)
+    ///     .fetch("<my user agent>")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn repos(&self) -> RepoList {
+        RepoList::new(self.repos_url.clone())
+    }
+}
+
+/// The full profile of a [Github] user or organization, with its stats.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UserProfile {
+    #[serde(flatten)]
+    user: User,
+    name: Option<String>,
+    company: Option<String>,
+    /// Website, or an empty string.
+    blog: Option<String>,
+    location: Option<String>,
+    /// Only set if the user made it public.
+    email: Option<String>,
+    #[serde(default)]
+    hireable: Option<bool>,
+    bio: Option<String>,
+    #[serde(default)]
+    twitter_username: Option<String>,
+    public_repos: u64,
+    public_gists: u64,
+    followers: u64,
+    following: u64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl UserProfile {
+    /// Gets the profile of `user`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> github_stats::Result<()> {
+    /// use github_stats::UserProfile;
+    ///
+    /// let profile = UserProfile::new("octocat", "<my user agent>").await?;
+    ///
+    /// println!("{} followers", profile.followers());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new(user: &str, user_agent: &str) -> Result<Self> {
+        Request::<UserProfile>::new(format!("{}/{}", URL, user)).fetch(user_agent).await
+    }
+
+    /// The login, ID and links of the user.
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn login(&self) -> &str {
+        self.user.login()
+    }
+
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    pub fn company(&self) -> &Option<String> {
+        &self.company
+    }
+
+    /// Website, or an empty string.
+    pub fn blog(&self) -> &Option<String> {
+        &self.blog
+    }
+
+    pub fn location(&self) -> &Option<String> {
+        &self.location
+    }
+
+    /// Only set if the user made it public.
+    pub fn email(&self) -> &Option<String> {
+        &self.email
+    }
+
+    pub fn hireable(&self) -> Option<bool> {
+        self.hireable
+    }
+
+    pub fn bio(&self) -> &Option<String> {
+        &self.bio
+    }
+
+    pub fn twitter_username(&self) -> &Option<String> {
+        &self.twitter_username
+    }
+
+    pub fn public_repos(&self) -> u64 {
+        self.public_repos
+    }
+
+    pub fn public_gists(&self) -> u64 {
+        self.public_gists
+    }
+
+    pub fn followers(&self) -> u64 {
+        self.followers
+    }
+
+    pub fn following(&self) -> u64 {
+        self.following
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
+
+/// The user an authorization token belongs to, with private stats.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> github_stats::Result<()> {
+/// use github_stats::AuthenticatedUser;
+///
+/// let user = AuthenticatedUser::new("<token>", "<my user agent>").await?;
+/// let missing = user.missing_scopes(&["repo", "read:org"]);
+///
+/// if !missing.is_empty() {
+///     eprintln!("{} needs a token with {}", user.profile().login(), missing.join(", "));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuthenticatedUser {
+    #[serde(flatten)]
+    profile: UserProfile,
+    #[serde(default)]
+    private_gists: Option<u64>,
+    #[serde(default)]
+    total_private_repos: Option<u64>,
+    #[serde(default)]
+    owned_private_repos: Option<u64>,
+    /// In *kilo*bytes.
+    #[serde(default)]
+    disk_usage: Option<u64>,
+    #[serde(default)]
+    collaborators: Option<u64>,
+    #[serde(default)]
+    two_factor_authentication: Option<bool>,
+    #[serde(default)]
+    plan: Option<Plan>,
+    /// From the `X-OAuth-Scopes` header, rather than the body.
+    #[serde(default)]
+    scopes: Option<Vec<String>>,
+}
+
+/// A user's [Github] plan.
+///
+/// [Github]: https://github.com/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Plan {
+    /// Such as `free` or `pro`.
+    name: String,
+    space: u64,
+    private_repos: u64,
+    #[serde(default)]
+    collaborators: Option<u64>,
+}
+
+impl AuthenticatedUser {
+    /// Gets the user that `token` belongs to.
+    pub async fn new(token: &str, user_agent: &str) -> Result<Self> {
+        let response = Request::<AuthenticatedUser>::new(String::from(CURRENT_USER_URL))
+            .authorization(token)
+            .send(user_agent)
+            .await?;
+        let scopes = match response.headers().get(OAUTH_SCOPES) {
+            Some(header) => Some(parse_scopes(header.to_str()?)),
+            None => None,
+        };
+        let mut user: AuthenticatedUser = response.json().await?;
+        user.scopes = scopes;
+        Ok(user)
+    }
+
+    /// The public profile of the user.
+    pub fn profile(&self) -> &UserProfile {
+        &self.profile
+    }
+
+    pub fn private_gists(&self) -> Option<u64> {
+        self.private_gists
+    }
+
+    pub fn total_private_repos(&self) -> Option<u64> {
+        self.total_private_repos
+    }
+
+    pub fn owned_private_repos(&self) -> Option<u64> {
+        self.owned_private_repos
+    }
+
+    /// In *kilo*bytes.
+    pub fn disk_usage(&self) -> Option<u64> {
+        self.disk_usage
+    }
+
+    pub fn collaborators(&self) -> Option<u64> {
+        self.collaborators
+    }
+
+    pub fn two_factor_authentication(&self) -> Option<bool> {
+        self.two_factor_authentication
+    }
+
+    pub fn plan(&self) -> &Option<Plan> {
+        &self.plan
+    }
+
+    /// OAuth scopes of the token, such as `repo`.
+    ///
+    /// `None` for tokens without scopes, such as fine-grained personal
+    /// access tokens.
+    pub fn scopes(&self) -> &Option<Vec<String>> {
+        &self.scopes
+    }
+
+    /// The `required` scopes that the token's scopes don't cover.
+    ///
+    /// Broader scopes cover narrower ones, so `repo` covers `public_repo`
+    /// and `admin:org` covers `read:org`. Every scope is missing for tokens
+    /// without scopes.
+    pub fn missing_scopes<'a>(&self, required: &[&'a str]) -> Vec<&'a str> {
+        let granted = self.scopes.as_deref().unwrap_or(&[]);
+        required
+            .iter()
+            .copied()
+            .filter(|r| !granted.iter().any(|g| covers(g, r)))
+            .collect()
+    }
+}
+
+impl Plan {
+    /// Such as `free` or `pro`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn space(&self) -> u64 {
+        self.space
+    }
+
+    pub fn private_repos(&self) -> u64 {
+        self.private_repos
+    }
+
+    pub fn collaborators(&self) -> Option<u64> {
+        self.collaborators
+    }
+}
+
+// Splits a header such as `repo, read:org`.
+fn parse_scopes(header: &str) -> Vec<String> {
+    header
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// If the `granted` scope includes the `required` one.
+fn covers(granted: &str, required: &str) -> bool {
+    if granted == required {
+        return true;
+    }
+    match granted {
+        "repo" => matches!(
+            required,
+            "repo:status" | "repo_deployment" | "public_repo" | "repo:invite" | "security_events"
+        ),
+        "user" => matches!(required, "read:user" | "user:email" | "user:follow"),
+        "project" => required == "read:project",
+        "codespace" => required == "codespace:secrets",
+        "admin:enterprise" => matches!(
+            required,
+            "manage_runners:enterprise" | "manage_billing:enterprise" | "read:enterprise"
+        ),
+        "manage_billing:enterprise" => required == "read:enterprise",
+        _ => match (granted.split_once(':'), required.split_once(':')) {
+            (Some((level, resource)), Some((needed, other))) if resource == other => {
+                matches!((level, needed), ("admin", "write") | ("admin", "read") | ("write", "read"))
+            }
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{RepoSort, RepoType};
+
+    /// A user as [Github] includes it in other resources.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn user(login: &str) -> Value {
+        json!({
+            "login": login,
+            "id": 1,
+            "node_id": "MDQ6VXNlcjE=",
+            "avatar_url": format!("https://github.com/images/error/{}_happy.gif", login),
+            "gravatar_id": "",
+            "html_url": format!("https://github.com/{}", login),
+            "url": format!("https://api.github.com/users/{}", login),
+            "followers_url": format!("https://api.github.com/users/{}/followers", login),
+            "following_url": format!("https://api.github.com/users/{}/following{{/other_user}}", login),
+            "gists_url": format!("https://api.github.com/users/{}/gists{{/gist_id}}", login),
+            "starred_url": format!("https://api.github.com/users/{}/starred{{/owner}}{{/repo}}", login),
+            "subscriptions_url": format!("https://api.github.com/users/{}/subscriptions", login),
+            "organizations_url": format!("https://api.github.com/users/{}/orgs", login),
+            "repos_url": format!("https://api.github.com/users/{}/repos", login),
+            "events_url": format!("https://api.github.com/users/{}/events{{/privacy}}", login),
+            "received_events_url": format!("https://api.github.com/users/{}/received_events", login),
+            "type": "User",
+            "site_admin": false
+        })
+    }
+
+    /// A user as [Github] sends it from `/users/{login}`.
+    ///
+    /// [Github]: https://github.com/
+    pub(crate) fn profile(login: &str) -> Value {
+        let mut profile = user(login);
+        let fields = json!({
+            "name": "monalisa octocat",
+            "company": "GitHub",
+            "blog": "https://github.com/blog",
+            "location": "San Francisco",
+            "email": null,
+            "hireable": false,
+            "bio": "There once was...",
+            "twitter_username": "monatheoctocat",
+            "public_repos": 2,
+            "public_gists": 1,
+            "followers": 20,
+            "following": 0,
+            "created_at": "2008-01-14T04:33:35Z",
+            "updated_at": "2008-01-14T04:33:35Z"
+        });
+        profile.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        profile
+    }
+
+    #[test]
+    fn full_profile() {
+        let profile: UserProfile = serde_json::from_value(profile("octocat")).unwrap();
+
+        assert_eq!("octocat", profile.login());
+        assert_eq!(Some("GitHub"), profile.company().as_deref());
+        assert_eq!(None, profile.email().as_deref());
+        assert_eq!(20, profile.followers());
+        assert_eq!(
+            "https://api.github.com/users/octocat",
+            profile.user().profile().to_string(),
+        );
+        assert_eq!(
+            "https://api.github.com/users/octocat/repos?type=all&sort=full_name&per_page=100",
+            profile.user().repos().r#type(RepoType::All).sort(RepoSort::FullName).to_string(),
+        );
+    }
+
+    #[test]
+    fn token_scopes() {
+        let mut user = profile("octocat");
+        user["total_private_repos"] = json!(100);
+        user["plan"] = json!({
+            "name": "Medium",
+            "space": 400,
+            "private_repos": 20,
+            "collaborators": 0
+        });
+        let mut user: AuthenticatedUser = serde_json::from_value(user).unwrap();
+        user.scopes = Some(parse_scopes("repo, admin:org,  gist, project"));
+
+        assert_eq!(Some(100), user.total_private_repos());
+        assert_eq!(None, user.disk_usage());
+        assert_eq!(Some("Medium"), user.plan().as_ref().map(Plan::name));
+        assert_eq!(
+            vec!["workflow", "write:packages"],
+            user.missing_scopes(&[
+                "public_repo",
+                "read:org",
+                "workflow",
+                "gist",
+                "read:project",
+                "write:packages",
+            ]),
+        );
+    }
 }